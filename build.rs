@@ -1,14 +1,14 @@
 use anyhow::Result;
 use convert_case::{Case, Casing};
-use flate2::write::GzEncoder;
+use flate2::{read::GzDecoder, write::GzEncoder};
 use minify_html::{minify, Cfg};
 use minijinja::Environment;
 use serde_json::json;
 use std::{
     collections::HashMap,
-    env::current_dir,
+    env::{self, current_dir},
     fs::{self, File},
-    io::Write,
+    io::{Read, Write},
     path::{Path, PathBuf},
     process::Command,
 };
@@ -17,10 +17,25 @@ type Map = HashMap<String, String>;
 type NestedMap = HashMap<&'static str, Map>;
 
 fn main() -> Result<()> {
-    if option_env!("BUILD_ICONS").is_none() {
-        return Ok(());
+    // like the icons, the committed header is only regenerated on demand
+    #[cfg(feature = "cdylib")]
+    {
+        println!("cargo:rerun-if-env-changed=BUILD_HEADER");
+        if std::env::var_os("BUILD_HEADER").is_some() {
+            generate_header()?;
+        }
     }
 
+    println!("cargo:rerun-if-env-changed=BUILD_ICONS");
+    println!("cargo:rerun-if-changed=src/gen");
+    if env::var_os("BUILD_ICONS").is_some() {
+        generate_icons()?;
+    }
+    write_names()
+}
+
+/// regenerate `src/gen` from the icons of `icon_resources`
+fn generate_icons() -> Result<()> {
     let cfg = Cfg {
         keep_closing_tags: true,
         keep_spaces_between_attributes: true,
@@ -84,6 +99,70 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// write the sorted names of the icons of the enabled sets into `OUT_DIR`, for
+/// `icon!` to check them at compile time
+fn write_names() -> Result<()> {
+    let gen_dir = current_dir()?.join("src/gen");
+    let mut out = String::new();
+    for set in [
+        "bootstrap",
+        "feather",
+        "font-awesome",
+        "heroicons",
+        "ionicons",
+        "octicons",
+    ] {
+        if env::var_os(format!("CARGO_FEATURE_{}", set.to_case(Case::UpperSnake))).is_none() {
+            continue;
+        }
+        let module = set.to_case(Case::Snake);
+        let mut variants: Vec<_> = decap(&fs::read(gen_dir.join(format!("{module}.bin")))?)?
+            .into_iter()
+            .filter(|(_, icons)| !icons.is_empty())
+            .collect();
+        variants.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        out.push_str(&format!("pub mod {module} {{\n"));
+        for (variant, icons) in variants {
+            let mut names: Vec<_> = icons.into_keys().collect();
+            names.sort_unstable();
+            out.push_str(&format!(
+                "    pub const {}: &[&str] = &{names:?};\n",
+                variant.to_case(Case::UpperSnake)
+            ));
+        }
+        out.push_str("}\n");
+    }
+    fs::write(Path::new(&env::var("OUT_DIR")?).join("names.rs"), out)?;
+    Ok(())
+}
+
+/// generate the C header of the `cdylib` feature from `src/capi.rs`
+#[cfg(feature = "cdylib")]
+fn generate_header() -> Result<()> {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("FREE_ICONS_H".into()),
+        autogen_warning: Some(
+            "// DO NOT CHANGE THIS FILE. IT IS AUTOMATICALLY GENERATED BY build.rs.".into(),
+        ),
+        documentation_style: cbindgen::DocumentationStyle::C99,
+        usize_is_size_t: true,
+        enumeration: cbindgen::EnumConfig {
+            rename_variants: cbindgen::RenameRule::ScreamingSnakeCase,
+            prefix_with_name: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(current_dir()?.join("src/capi.rs"))
+        .generate()?
+        .write_to_file(current_dir()?.join("include/free_icons.h"));
+    Ok(())
+}
+
 fn get_path(name: &str, inner_path: &str) -> PathBuf {
     current_dir()
         .unwrap()
@@ -182,6 +261,13 @@ fn get_engine() -> Result<Environment<'static>> {
     Ok(env)
 }
 
+fn decap(bin: &[u8]) -> Result<HashMap<String, Map>> {
+    let mut uncompressed = Vec::new();
+    GzDecoder::new(bin).read_to_end(&mut uncompressed)?;
+    let (data, _) = bincode::decode_from_slice(&uncompressed, bincode::config::standard())?;
+    Ok(data)
+}
+
 fn encap(data: &NestedMap) -> Result<Vec<u8>> {
     let bin = bincode::encode_to_vec(data, bincode::config::standard())?;
     let buf = Vec::new();
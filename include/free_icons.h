@@ -0,0 +1,78 @@
+#ifndef FREE_ICONS_H
+#define FREE_ICONS_H
+
+// DO NOT CHANGE THIS FILE. IT IS AUTOMATICALLY GENERATED BY build.rs.
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// the result of the functions of the C API
+typedef enum FreeIconsError {
+  FREE_ICONS_ERROR_OK = 0,
+  // a pointer is null, or a string is not UTF-8
+  FREE_ICONS_ERROR_INVALID_ARGUMENT = 1,
+  // see `IconError::Parse`
+  FREE_ICONS_ERROR_PARSE = 2,
+  // see `IconError::UnknownIconType`
+  FREE_ICONS_ERROR_UNKNOWN_ICON_TYPE = 3,
+  // see `IconError::NotFound`
+  FREE_ICONS_ERROR_NOT_FOUND = 4,
+  // see `IconError::Render`
+  FREE_ICONS_ERROR_RENDER = 5,
+} FreeIconsError;
+
+// the SVG of the icon with the canonical id, e.g. `heroicons:outline:trash`
+//
+// # Safety
+//
+// `id` must be a valid C string, and `out` a valid pointer.
+enum FreeIconsError free_icons_get(const char *id, char **out);
+
+// the icon as a standalone XML document, with the attributes given as `len`
+// names and values, e.g. `class` and `h-4`. They replace the attributes of
+// the icon, and their values are escaped. An invalid attribute name, or an
+// icon which cannot be rendered, returns `FREE_ICONS_ERROR_RENDER`.
+//
+// # Safety
+//
+// `id` must be a valid C string, `names` and `values` arrays of `len` valid C
+// strings (or null if `len` is 0), and `out` a valid pointer.
+enum FreeIconsError free_icons_render(const char *id,
+                                      const char *const *names,
+                                      const char *const *values,
+                                      size_t len,
+                                      char **out);
+
+// the icon types, e.g. `heroicons:outline`
+//
+// # Safety
+//
+// `out` and `len` must be valid pointers.
+enum FreeIconsError free_icons_icon_types(char ***out, size_t *len);
+
+// the names of the icons of an icon type, e.g. `heroicons:outline`
+//
+// # Safety
+//
+// `icon_type` must be a valid C string, and `out` and `len` valid pointers.
+enum FreeIconsError free_icons_names(const char *icon_type, char ***out, size_t *len);
+
+// release a string returned by the library
+//
+// # Safety
+//
+// `s` must be null or a string returned by the library, released only once.
+void free_icons_free_string(char *s);
+
+// release an array of strings returned by the library
+//
+// # Safety
+//
+// `strings` must be null or an array returned by the library with its
+// length, released only once.
+void free_icons_free_strings(char **strings, size_t len);
+
+#endif  /* FREE_ICONS_H */
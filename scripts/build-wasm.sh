@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+# build the npm package of the `wasm` feature into `pkg/`: the module bound for
+# Node (`pkg/node`) and for bundlers (`pkg/bundler`), picked by the `exports`
+# of `pkg/package.json`. It needs the wasm32-unknown-unknown target, and a
+# wasm-bindgen-cli of the same version as the wasm-bindgen crate:
+#
+#   rustup target add wasm32-unknown-unknown
+#   cargo install wasm-bindgen-cli --version "$(cargo pkgid wasm-bindgen | sed 's/.*@//')"
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+version=$(cargo pkgid | sed 's/.*@//')
+bindgen=$(cargo pkgid wasm-bindgen | sed 's/.*@//')
+if [ "$(wasm-bindgen --version)" != "wasm-bindgen $bindgen" ]; then
+  echo "wasm-bindgen-cli $bindgen is needed, found: $(wasm-bindgen --version)" >&2
+  exit 1
+fi
+
+# wasm-pack would need the cdylib crate type in Cargo.toml, which every other
+# build of the crate would then pay for
+cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+wasm=target/wasm32-unknown-unknown/release/free_icons.wasm
+rm -rf pkg
+wasm-bindgen --target nodejs --out-dir pkg/node "$wasm"
+wasm-bindgen --target bundler --out-dir pkg/bundler "$wasm"
+
+cat > pkg/package.json <<EOF
+{
+  "name": "free-icons",
+  "version": "$version",
+  "description": "The icons of the free-icons crate, with the same rendering as in Rust",
+  "license": "MIT",
+  "repository": "https://github.com/tyrchen/free-icons",
+  "main": "./node/free_icons.js",
+  "types": "./bundler/free_icons.d.ts",
+  "exports": {
+    ".": {
+      "types": "./bundler/free_icons.d.ts",
+      "node": "./node/free_icons.js",
+      "default": "./bundler/free_icons.js"
+    }
+  },
+  "sideEffects": ["./bundler/free_icons.js", "./bundler/snippets/*"]
+}
+EOF
+echo "pkg/package.json"
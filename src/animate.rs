@@ -0,0 +1,251 @@
+use crate::{next_id_prefix, Element, IconDocument, ViewBox};
+use std::time::Duration;
+
+/// rotate the icon around its center
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spin {
+    pub duration: Duration,
+}
+
+/// fade the icon in and out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pulse {
+    pub duration: Duration,
+}
+
+/// wiggle the icon around its top center, like a ringing bell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shake {
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Animation {
+    Spin(Spin),
+    Pulse(Pulse),
+    Shake(Shake),
+}
+
+/// how an animation is embedded into the icon
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationMode {
+    /// a scoped `<style>` with a unique keyframe name
+    #[default]
+    Css,
+    /// SMIL `<animate>`/`<animateTransform>` elements, which also play when the
+    /// icon is used as an image. As media queries can't stop SMIL animations,
+    /// `Css` is used instead when the reduced motion guard is on.
+    Smil,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct AnimationSettings {
+    pub(crate) animation: Option<Animation>,
+    pub(crate) mode: AnimationMode,
+    pub(crate) reduced_motion: bool,
+}
+
+impl From<Spin> for Animation {
+    fn from(v: Spin) -> Self {
+        Animation::Spin(v)
+    }
+}
+
+impl From<Pulse> for Animation {
+    fn from(v: Pulse) -> Self {
+        Animation::Pulse(v)
+    }
+}
+
+impl From<Shake> for Animation {
+    fn from(v: Shake) -> Self {
+        Animation::Shake(v)
+    }
+}
+
+impl Animation {
+    fn name(&self) -> &'static str {
+        match self {
+            Animation::Spin(_) => "spin",
+            Animation::Pulse(_) => "pulse",
+            Animation::Shake(_) => "shake",
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        match self {
+            Animation::Spin(v) => v.duration,
+            Animation::Pulse(v) => v.duration,
+            Animation::Shake(v) => v.duration,
+        }
+    }
+
+    fn keyframes(&self) -> &'static str {
+        match self {
+            Animation::Spin(_) => "to{transform:rotate(360deg)}",
+            Animation::Pulse(_) => "50%{opacity:.4}",
+            Animation::Shake(_) => {
+                "0%,50%,to{transform:rotate(0)}10%,30%{transform:rotate(-10deg)}20%,40%{transform:rotate(10deg)}"
+            }
+        }
+    }
+
+    fn css(&self, name: &str, dur: &str) -> String {
+        let (origin, timing) = match self {
+            Animation::Spin(_) => ("center", "linear"),
+            Animation::Pulse(_) => ("center", "ease-in-out"),
+            Animation::Shake(_) => ("top", "ease-in-out"),
+        };
+        format!(
+            ".{name}{{transform-box:view-box;transform-origin:{origin};animation:{name} {dur} {timing} infinite}}"
+        )
+    }
+
+    fn smil(&self, view_box: &ViewBox, dur: &str) -> Element {
+        let cx = view_box.min_x + view_box.width / 2.0;
+        let cy = view_box.min_y + view_box.height / 2.0;
+        let top = view_box.min_y;
+
+        let mut el = match self {
+            Animation::Spin(_) => {
+                let mut el = Element::new("animateTransform");
+                el.set_attr("attributeName", "transform")
+                    .set_attr("type", "rotate")
+                    .set_attr("from", format!("0 {cx} {cy}"))
+                    .set_attr("to", format!("360 {cx} {cy}"));
+                el
+            }
+            Animation::Pulse(_) => {
+                let mut el = Element::new("animate");
+                el.set_attr("attributeName", "opacity")
+                    .set_attr("values", "1;.4;1")
+                    .set_attr("keyTimes", "0;.5;1")
+                    .set_attr("calcMode", "spline")
+                    .set_attr("keySplines", ".4 0 .6 1;.4 0 .6 1");
+                el
+            }
+            Animation::Shake(_) => {
+                let mut el = Element::new("animateTransform");
+                let values = [0, -10, 10, -10, 10, 0, 0]
+                    .iter()
+                    .map(|deg| format!("{deg} {cx} {top}"))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                el.set_attr("attributeName", "transform")
+                    .set_attr("type", "rotate")
+                    .set_attr("values", values)
+                    .set_attr("keyTimes", "0;.1;.2;.3;.4;.5;1");
+                el
+            }
+        };
+        el.set_attr("dur", dur)
+            .set_attr("repeatCount", "indefinite");
+        el
+    }
+}
+
+impl AnimationSettings {
+    /// wrap the content of the icon into an animated group
+    pub(crate) fn apply(&self, doc: &mut IconDocument) {
+        let Some(animation) = self.animation else {
+            return;
+        };
+        let dur = format!("{}ms", animation.duration().as_millis());
+        let view_box = doc.view_box().unwrap_or(ViewBox {
+            min_x: 0.0,
+            min_y: 0.0,
+            width: 24.0,
+            height: 24.0,
+        });
+
+        let root = doc.root_mut();
+        let mut group = Element::new("g");
+        group.children_mut().append(root.children_mut());
+
+        if self.mode == AnimationMode::Smil && !self.reduced_motion {
+            group.push(animation.smil(&view_box, &dur));
+            root.push(group);
+            return;
+        }
+
+        let name = format!("{}-{}", next_id_prefix(), animation.name());
+        let mut rule = animation.css(&name, &dur);
+        if self.reduced_motion {
+            rule = format!("@media (prefers-reduced-motion:no-preference){{{rule}}}");
+        }
+        let mut style = Element::new("style");
+        style.push(format!(
+            "@keyframes {name}{{{}}}{rule}",
+            animation.keyframes()
+        ));
+        group.set_attr("class", name);
+        root.push(style).push(group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str =
+        r#"<svg viewbox="0 0 24 24" xmlns=http://www.w3.org/2000/svg><path d="M12 3v18"/></svg>"#;
+
+    fn animate(settings: AnimationSettings) -> String {
+        let mut doc = IconDocument::parse(SVG).unwrap();
+        settings.apply(&mut doc);
+        doc.to_string()
+    }
+
+    #[test]
+    fn css_animation_should_use_unique_keyframes() {
+        let settings = AnimationSettings {
+            animation: Some(
+                Spin {
+                    duration: Duration::from_secs(1),
+                }
+                .into(),
+            ),
+            ..Default::default()
+        };
+        let first = animate(settings);
+        assert!(first.contains("<style>@keyframes fi"));
+        assert!(first.contains("animation:fi"));
+        assert!(first.contains(" 1000ms linear infinite}</style><g class=fi"));
+        assert_ne!(first, animate(settings));
+    }
+
+    #[test]
+    fn reduced_motion_should_guard_animations() {
+        let settings = AnimationSettings {
+            animation: Some(
+                Pulse {
+                    duration: Duration::from_millis(1500),
+                }
+                .into(),
+            ),
+            mode: AnimationMode::Smil,
+            reduced_motion: true,
+        };
+        let svg = animate(settings);
+        assert!(svg.contains("@media (prefers-reduced-motion:no-preference){."));
+        assert!(!svg.contains("<animate"));
+    }
+
+    #[test]
+    fn smil_animation_should_work() {
+        let settings = AnimationSettings {
+            animation: Some(
+                Shake {
+                    duration: Duration::from_millis(800),
+                }
+                .into(),
+            ),
+            mode: AnimationMode::Smil,
+            reduced_motion: false,
+        };
+        assert_eq!(
+            animate(settings),
+            r#"<svg viewbox="0 0 24 24" xmlns=http://www.w3.org/2000/svg><g><path d="M12 3v18"/><animateTransform attributeName=transform type=rotate values="0 12 0;-10 12 0;10 12 0;-10 12 0;10 12 0;0 12 0;0 12 0" keyTimes=0;.1;.2;.3;.4;.5;1 dur=800ms repeatCount=indefinite /></g></svg>"#
+        );
+    }
+}
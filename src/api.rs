@@ -0,0 +1,259 @@
+use crate::{
+    icon, search,
+    server::{closest, icon_url},
+    Icon, IconType,
+};
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+/// a router for icon pickers, to be merged with `icon_router`:
+///
+/// - `/api/icons?q=arrow&set=feather&limit=50&cursor=...` lists the icons
+///   matching the query, in the order of `search`, or all the icons in the
+///   order of `IconType::all` without a query. Pass the `next` cursor of a
+///   page to get the next one.
+/// - `/api/icons/{set}/{variant}/{name}` describes an icon.
+pub fn api_router() -> Router {
+    Router::new()
+        .route("/api/icons", get(list_icons))
+        .route("/api/icons/{set}/{variant}/{name}", get(describe_icon))
+}
+
+async fn list_icons(Query(query): Query<Vec<(String, String)>>) -> Response {
+    let mut q = String::new();
+    let mut types = Vec::new();
+    let mut limit = DEFAULT_LIMIT;
+    let mut cursor = None;
+    for (key, value) in &query {
+        match key.as_str() {
+            "q" => q = value.clone(),
+            "set" => match IconType::matching(value) {
+                Ok(matching) => types.extend(matching),
+                Err(e) => return error(StatusCode::BAD_REQUEST, e.to_string(), &[]),
+            },
+            "limit" => match value.parse() {
+                Ok(n) if (1..=MAX_LIMIT).contains(&n) => limit = n,
+                _ => {
+                    let reason = format!("`limit` must be between 1 and {MAX_LIMIT}");
+                    return error(StatusCode::BAD_REQUEST, reason, &[]);
+                }
+            },
+            "cursor" => cursor = Some(value.as_str()),
+            _ => {
+                let reason = format!("unknown parameter `{key}`, expected q, set, limit or cursor");
+                return error(StatusCode::BAD_REQUEST, reason, &[]);
+            }
+        }
+    }
+
+    let icons: Vec<_> = if q.trim().is_empty() {
+        let all = if types.is_empty() {
+            IconType::all()
+        } else {
+            types
+        };
+        all.into_iter()
+            .flat_map(|t| t.names().into_iter().filter_map(move |name| icon(t, name)))
+            .collect()
+    } else {
+        search(&q)
+            .into_iter()
+            .filter(|icon| types.is_empty() || types.contains(&icon.icon_type()))
+            .collect()
+    };
+    // the cursor is the id of the last icon of the previous page
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => match icons.iter().position(|icon| icon.id() == cursor) {
+            Some(pos) => pos + 1,
+            None => {
+                let reason = format!("invalid cursor: {cursor}");
+                return error(StatusCode::BAD_REQUEST, reason, &[]);
+            }
+        },
+    };
+    let page = &icons[start..(start + limit).min(icons.len())];
+    let next = match page.last() {
+        Some(last) if start + page.len() < icons.len() => Value::from(last.id()),
+        _ => Value::Null,
+    };
+    let body = json!({
+        "total": icons.len(),
+        "icons": page.iter().map(summary).collect::<Vec<_>>(),
+        "next": next,
+    });
+    json_response(StatusCode::OK, &body)
+}
+
+async fn describe_icon(Path((set, variant, name)): Path<(String, String, String)>) -> Response {
+    let icon_type = format!("{set}:{variant}").parse::<IconType>().ok();
+    let Some(icon) = icon_type.and_then(|t| icon(t, &name)) else {
+        let reason = format!("icon not found: {set}:{variant}:{name}");
+        return error(StatusCode::NOT_FOUND, reason, &closest(icon_type, &name));
+    };
+
+    let mut body = summary(&icon);
+    let meta = icon.meta();
+    body["viewBox"] = meta.view_box.map(|v| v.to_string()).into();
+    body["hasIds"] = meta.has_ids.into();
+    // the same icon in the other variants of the set
+    body["variants"] = IconType::all()
+        .into_iter()
+        .filter(|t| t.set() == icon.set())
+        .filter_map(|t| self::icon(t, icon.name()))
+        .map(|icon| json!({ "variant": icon.variant(), "id": icon.id(), "url": icon_url(&icon) }))
+        .collect::<Vec<_>>()
+        .into();
+    json_response(StatusCode::OK, &body)
+}
+
+fn summary(icon: &Icon) -> Value {
+    json!({
+        "id": icon.id(),
+        "set": icon.set(),
+        "variant": icon.variant(),
+        "name": icon.name(),
+        "tags": icon.tags(),
+        "url": icon_url(icon),
+    })
+}
+
+fn error(status: StatusCode, reason: String, suggestions: &[Icon]) -> Response {
+    let suggestions: Vec<_> = suggestions.iter().map(summary).collect();
+    json_response(
+        status,
+        &json!({ "error": reason, "suggestions": suggestions }),
+    )
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn get_json(uri: &str) -> (StatusCode, Value) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = api_router().oneshot(request).await.unwrap();
+        let status = response.status();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn ids(body: &Value) -> Vec<&str> {
+        body["icons"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|icon| icon["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[cfg(feature = "feather")]
+    #[tokio::test]
+    async fn api_should_page_through_icons() {
+        let (status, body) = get_json("/api/icons?q=arrow&set=feather&limit=3").await;
+        assert_eq!(status, StatusCode::OK);
+        let total = body["total"].as_u64().unwrap() as usize;
+        assert!(total > 3);
+        let first = ids(&body);
+        assert_eq!(first.len(), 3);
+        assert!(first
+            .iter()
+            .all(|id| id.starts_with("feather:normal:arrow")));
+        assert_eq!(
+            body["icons"][0]["url"],
+            format!("/icons/feather/normal/{}.svg", &first[0][15..])
+        );
+
+        // following the cursors lists every icon once
+        let mut all: Vec<String> = first.iter().map(|id| id.to_string()).collect();
+        let mut next = body["next"].as_str().unwrap().to_owned();
+        loop {
+            let (_, body) = get_json(&format!(
+                "/api/icons?q=arrow&set=feather&limit=3&cursor={next}"
+            ))
+            .await;
+            all.extend(ids(&body).iter().map(|id| id.to_string()));
+            match body["next"].as_str() {
+                Some(cursor) => next = cursor.to_owned(),
+                None => break,
+            }
+        }
+        assert_eq!(all.len(), total);
+        let (_, body) = get_json("/api/icons?q=arrow&set=feather&limit=500").await;
+        assert_eq!(ids(&body), all);
+        assert!(body["next"].is_null());
+
+        // without a query, all the icons of the set in order
+        let (_, body) = get_json("/api/icons?set=feather&limit=2").await;
+        assert_eq!(
+            ids(&body),
+            ["feather:normal:activity", "feather:normal:airplay"]
+        );
+        assert_eq!(
+            body["total"],
+            IconType::Feather(crate::Feather::Normal).names().len()
+        );
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[tokio::test]
+    async fn api_should_describe_icons() {
+        let (status, body) = get_json("/api/icons/heroicons/outline/arrow-left").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], "heroicons:outline:arrow-left");
+        assert_eq!(
+            body["tags"],
+            json!(["heroicons", "outline", "arrow", "left"])
+        );
+        assert_eq!(body["viewBox"], "0 0 24 24");
+        assert_eq!(body["hasIds"], false);
+        assert_eq!(
+            body["variants"],
+            json!([
+                { "variant": "outline", "id": "heroicons:outline:arrow-left", "url": "/icons/heroicons/outline/arrow-left.svg" },
+                { "variant": "solid", "id": "heroicons:solid:arrow-left", "url": "/icons/heroicons/solid/arrow-left.svg" },
+            ])
+        );
+
+        let (status, body) = get_json("/api/icons/heroicons/outline/arow-left").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "icon not found: heroicons:outline:arow-left");
+        assert_eq!(body["suggestions"][0]["id"], "heroicons:outline:arrow-left");
+    }
+
+    #[tokio::test]
+    async fn api_should_reject_bad_queries() {
+        for uri in [
+            "/api/icons?set=nope",
+            "/api/icons?limit=0",
+            "/api/icons?cursor=nope:nope:nope",
+            "/api/icons?page=2",
+        ] {
+            let (status, body) = get_json(uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+            assert!(body["error"].is_string());
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use free_icons::{
+    search, svg_base64_data_uri, svg_data_uri, GalleryBuilder, Icon, IconError, IconType,
+};
+use serde_json::{json, Value};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// list, search and export the bundled icons
+#[derive(Debug, Parser)]
+#[command(name = "free-icons", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// list the icon sets, or the icons of a set, e.g. `heroicons:outline`
+    List {
+        set: Option<String>,
+        #[arg(long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
+    },
+    /// find the icons whose ids contain every word of the query
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// only search these sets
+        #[arg(long)]
+        set: Vec<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        #[arg(long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
+        /// show the results as a grid of previews instead of a list
+        #[cfg(feature = "raster")]
+        #[arg(long)]
+        preview: bool,
+        #[cfg(feature = "raster")]
+        #[command(flatten)]
+        preview_args: PreviewArgs,
+    },
+    /// draw icons in the terminal
+    #[cfg(feature = "raster")]
+    Preview {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        #[command(flatten)]
+        args: PreviewArgs,
+    },
+    /// print an icon, e.g. `bootstrap:fill:alarm`
+    Get {
+        id: String,
+        #[command(flatten)]
+        attrs: AttrArgs,
+        #[arg(long, value_enum, default_value_t = Format::Svg)]
+        format: Format,
+    },
+    /// write whole sets into a directory
+    Export {
+        /// the sets to export, e.g. `feather` or `heroicons:solid`
+        #[arg(long, required = true)]
+        set: Vec<String>,
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+        #[command(flatten)]
+        attrs: AttrArgs,
+        /// `svg` writes a file per icon, the others a file per set
+        #[arg(long, value_enum, default_value_t = Format::Svg)]
+        format: Format,
+    },
+    /// write a static HTML gallery of the icons into a directory
+    Gallery {
+        #[arg(long, default_value = "gallery")]
+        out: PathBuf,
+        /// only include these sets
+        #[arg(long)]
+        set: Vec<String>,
+        #[arg(long)]
+        title: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct AttrArgs {
+    #[arg(long)]
+    class: Option<String>,
+    /// the width and height of the icon
+    #[arg(long)]
+    size: Option<String>,
+    #[arg(long)]
+    fill: Option<String>,
+    #[arg(long)]
+    stroke: Option<String>,
+    #[arg(long)]
+    stroke_width: Option<String>,
+}
+
+#[cfg(feature = "raster")]
+#[derive(Debug, Args)]
+struct PreviewArgs {
+    /// the width of a preview in cells
+    #[arg(long, default_value_t = 12)]
+    width: u32,
+    /// draw with braille dots instead of half blocks
+    #[arg(long)]
+    braille: bool,
+    /// draw in one color, e.g. `#ffffff` for dark icons on a dark terminal
+    #[arg(long, value_parser = parse_rgb)]
+    tint: Option<[u8; 3]>,
+    /// draw in the color of the terminal text, without escape codes
+    #[arg(long)]
+    no_color: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Svg,
+    DataUri,
+    Base64,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ListFormat {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        // e.g. `free-icons list heroicons | head`
+        Err(e) if is_broken_pipe(e.as_ref()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let mut out = io::stdout().lock();
+    match cli.command {
+        Command::List { set: None, format } => {
+            let types = IconType::all();
+            match format {
+                ListFormat::Text => {
+                    for icon_type in types {
+                        writeln!(out, "{icon_type}\t{}", icon_type.names().len())?;
+                    }
+                }
+                ListFormat::Json => {
+                    let types: Vec<_> = types
+                        .iter()
+                        .map(|t| json!({ "set": t.set(), "variant": t.variant(), "count": t.names().len() }))
+                        .collect();
+                    writeln!(out, "{}", Value::Array(types))?;
+                }
+            }
+        }
+        Command::List {
+            set: Some(set),
+            format,
+        } => {
+            let ids = IconType::matching(&set)?
+                .into_iter()
+                .flat_map(|t| t.names().into_iter().map(move |name| format!("{t}:{name}")));
+            print_ids(&mut out, ids, format)?;
+        }
+        Command::Search {
+            query,
+            set,
+            limit,
+            format,
+            #[cfg(feature = "raster")]
+            preview,
+            #[cfg(feature = "raster")]
+            preview_args,
+        } => {
+            let types = set
+                .iter()
+                .map(|s| IconType::matching(s))
+                .collect::<Result<Vec<_>, _>>()?
+                .concat();
+            let icons = search(&query.join(" "))
+                .into_iter()
+                .filter(|icon| types.is_empty() || types.contains(&icon.icon_type()))
+                .take(limit);
+            #[cfg(feature = "raster")]
+            if preview {
+                let icons: Vec<_> = icons.collect();
+                return print_previews(&mut out, &icons, &preview_args);
+            }
+            print_ids(&mut out, icons.map(|icon| icon.id()), format)?;
+        }
+        #[cfg(feature = "raster")]
+        Command::Preview { ids, args } => {
+            let icons = ids
+                .iter()
+                .map(|id| id.parse())
+                .collect::<Result<Vec<Icon>, _>>()?;
+            print_previews(&mut out, &icons, &args)?;
+        }
+        Command::Get { id, attrs, format } => {
+            let icon: Icon = id.parse()?;
+            let svg = render(&icon, &attrs)?;
+            let output = match format {
+                Format::Svg => svg,
+                Format::DataUri => svg_data_uri(&svg),
+                Format::Base64 => svg_base64_data_uri(&svg),
+                Format::Json => to_json(&icon, &svg).to_string(),
+            };
+            writeln!(out, "{output}")?;
+        }
+        Command::Export {
+            set,
+            out: dir,
+            attrs,
+            format,
+        } => {
+            for set in &set {
+                for icon_type in IconType::matching(set)? {
+                    let count = export(icon_type, &dir, &attrs, format)?;
+                    writeln!(out, "{icon_type}\t{count}")?;
+                }
+            }
+        }
+        Command::Gallery {
+            out: dir,
+            set,
+            title,
+        } => {
+            let mut gallery = GalleryBuilder::new();
+            if !set.is_empty() {
+                let types = set
+                    .iter()
+                    .map(|s| IconType::matching(s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                gallery = gallery.types(types.concat());
+            }
+            if let Some(title) = title {
+                gallery = gallery.title(title);
+            }
+            gallery.write_to(&dir)?;
+            writeln!(out, "{}", dir.join("index.html").display())?;
+        }
+    }
+    Ok(())
+}
+
+fn print_ids(
+    out: &mut impl Write,
+    ids: impl Iterator<Item = String>,
+    format: ListFormat,
+) -> io::Result<()> {
+    match format {
+        ListFormat::Text => {
+            for id in ids {
+                writeln!(out, "{id}")?;
+            }
+            Ok(())
+        }
+        ListFormat::Json => writeln!(out, "{}", Value::from_iter(ids)),
+    }
+}
+
+/// the icons side by side, as many per row as the terminal is wide
+#[cfg(feature = "raster")]
+fn print_previews(
+    out: &mut impl Write,
+    icons: &[Icon],
+    args: &PreviewArgs,
+) -> Result<(), Box<dyn Error>> {
+    use free_icons::{TerminalMode, TerminalRenderer};
+
+    let mode = match args.braille {
+        true => TerminalMode::Braille,
+        false => TerminalMode::HalfBlock,
+    };
+    let mut renderer = TerminalRenderer::new()
+        .mode(mode)
+        .width(args.width)
+        .color(!args.no_color);
+    if let Some(rgb) = args.tint {
+        renderer = renderer.tint(rgb);
+    }
+    let columns = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(80);
+    let per_row = (columns + 2) / (args.width as usize + 2);
+    let icons = icons.iter().map(|icon| (icon.clone(), icon.id()));
+    write!(out, "{}", renderer.render_grid(icons, per_row)?)?;
+    Ok(())
+}
+
+#[cfg(feature = "raster")]
+fn parse_rgb(s: &str) -> Result<[u8; 3], String> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    let rgb = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| hex.len() == 6)
+        .ok_or_else(|| format!("expected a color like #ffffff, got {s}"))?;
+    Ok([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8])
+}
+
+/// the icon as a standalone `.svg` document. The attributes replace the ones
+/// of the icon, so that the document stays valid XML.
+fn render(icon: &Icon, attrs: &AttrArgs) -> Result<String, IconError> {
+    let size = attrs.size.as_deref();
+    let attrs = [
+        ("class", attrs.class.as_deref()),
+        ("fill", attrs.fill.as_deref()),
+        ("stroke", attrs.stroke.as_deref()),
+        ("stroke-width", attrs.stroke_width.as_deref()),
+        ("width", size),
+        ("height", size),
+    ];
+    let attrs = attrs.into_iter().filter_map(|(k, v)| Some((k, v?)));
+    Ok(icon.to_document_with(attrs)?.to_xml())
+}
+
+fn to_json(icon: &Icon, svg: &str) -> Value {
+    json!({
+        "id": icon.id(),
+        "set": icon.set(),
+        "variant": icon.variant(),
+        "name": icon.name(),
+        "svg": svg,
+        "dataUri": svg_data_uri(svg),
+    })
+}
+
+/// write `<set>/<variant>/<name>.svg` files, or a `<set>-<variant>` file with
+/// all the icons: one `name uri` line per icon, or a JSON array
+fn export(
+    icon_type: IconType,
+    dir: &Path,
+    attrs: &AttrArgs,
+    format: Format,
+) -> Result<usize, Box<dyn Error>> {
+    let names = icon_type.names();
+    let icons = names
+        .iter()
+        .filter_map(|name| free_icons::icon(icon_type, name));
+    let file = dir.join(format!("{}-{}", icon_type.set(), icon_type.variant()));
+    match format {
+        Format::Svg => {
+            let dir = dir.join(icon_type.set()).join(icon_type.variant());
+            fs::create_dir_all(&dir)?;
+            for icon in icons {
+                fs::write(
+                    dir.join(format!("{}.svg", icon.name())),
+                    render(&icon, attrs)?,
+                )?;
+            }
+        }
+        Format::DataUri | Format::Base64 => {
+            fs::create_dir_all(dir)?;
+            let mut lines = String::new();
+            for icon in icons {
+                let svg = render(&icon, attrs)?;
+                let uri = match format {
+                    Format::Base64 => svg_base64_data_uri(&svg),
+                    _ => svg_data_uri(&svg),
+                };
+                lines.push_str(&format!("{} {uri}\n", icon.name()));
+            }
+            fs::write(file.with_extension("txt"), lines)?;
+        }
+        Format::Json => {
+            fs::create_dir_all(dir)?;
+            let values = icons
+                .map(|icon| Ok(to_json(&icon, &render(&icon, attrs)?)))
+                .collect::<Result<Vec<_>, IconError>>()?;
+            fs::write(
+                file.with_extension("json"),
+                Value::Array(values).to_string(),
+            )?;
+        }
+    }
+    Ok(names.len())
+}
+
+fn is_broken_pipe(e: &(dyn Error + 'static)) -> bool {
+    e.downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "raster")]
+    #[test]
+    fn parse_rgb_should_work() {
+        use super::parse_rgb;
+
+        assert_eq!(parse_rgb("#ff8000"), Ok([255, 128, 0]));
+        assert_eq!(parse_rgb("0a0b0c"), Ok([10, 11, 12]));
+        assert!(parse_rgb("#fff").is_err());
+        assert!(parse_rgb("#gggggg").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{render::escaped_attr, Icon, IconError, IconType};
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+
+// the C API, declared in `include/free_icons.h`. Strings are NUL terminated
+// UTF-8, and the strings returned by the library must be released with
+// `free_icons_free_string` or `free_icons_free_strings`.
+
+/// the result of the functions of the C API
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreeIconsError {
+    Ok = 0,
+    /// a pointer is null, or a string is not UTF-8
+    InvalidArgument = 1,
+    /// see `IconError::Parse`
+    Parse = 2,
+    /// see `IconError::UnknownIconType`
+    UnknownIconType = 3,
+    /// see `IconError::NotFound`
+    NotFound = 4,
+    /// see `IconError::Render`
+    Render = 5,
+}
+
+impl From<IconError> for FreeIconsError {
+    fn from(e: IconError) -> Self {
+        match e {
+            IconError::Parse { .. } => FreeIconsError::Parse,
+            IconError::UnknownIconType(_) => FreeIconsError::UnknownIconType,
+            IconError::NotFound(_) => FreeIconsError::NotFound,
+            IconError::Render(_) => FreeIconsError::Render,
+        }
+    }
+}
+
+/// the SVG of the icon with the canonical id, e.g. `heroicons:outline:trash`
+///
+/// # Safety
+///
+/// `id` must be a valid C string, and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_get(
+    id: *const c_char,
+    out: *mut *mut c_char,
+) -> FreeIconsError {
+    free_icons_render(id, ptr::null(), ptr::null(), 0, out)
+}
+
+/// the icon as a standalone XML document, with the attributes given as `len`
+/// names and values, e.g. `class` and `h-4`. They replace the attributes of
+/// the icon, and their values are escaped. An invalid attribute name, or an
+/// icon which cannot be rendered, returns `FREE_ICONS_ERROR_RENDER`.
+///
+/// # Safety
+///
+/// `id` must be a valid C string, `names` and `values` arrays of `len` valid C
+/// strings (or null if `len` is 0), and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_render(
+    id: *const c_char,
+    names: *const *const c_char,
+    values: *const *const c_char,
+    len: usize,
+    out: *mut *mut c_char,
+) -> FreeIconsError {
+    if out.is_null() || (len > 0 && (names.is_null() || values.is_null())) {
+        return FreeIconsError::InvalidArgument;
+    }
+    let Some(id) = to_str(id) else {
+        return FreeIconsError::InvalidArgument;
+    };
+    let mut attrs = Vec::with_capacity(len);
+    for i in 0..len {
+        let (Some(name), Some(value)) = (to_str(*names.add(i)), to_str(*values.add(i))) else {
+            return FreeIconsError::InvalidArgument;
+        };
+        match escaped_attr(name, value) {
+            Ok(attr) => attrs.push(attr),
+            Err(e) => return e.into(),
+        }
+    }
+    let icon: Icon = match id.parse() {
+        Ok(icon) => icon,
+        Err(e) => return e.into(),
+    };
+    match icon.to_document_with(attrs) {
+        Ok(doc) => *out = to_c_string(doc.to_xml()),
+        Err(_) => return FreeIconsError::Render,
+    }
+    FreeIconsError::Ok
+}
+
+/// the icon types, e.g. `heroicons:outline`
+///
+/// # Safety
+///
+/// `out` and `len` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_icon_types(
+    out: *mut *mut *mut c_char,
+    len: *mut usize,
+) -> FreeIconsError {
+    let types = IconType::all().iter().map(|t| t.to_string()).collect();
+    write_strings(types, out, len)
+}
+
+/// the names of the icons of an icon type, e.g. `heroicons:outline`
+///
+/// # Safety
+///
+/// `icon_type` must be a valid C string, and `out` and `len` valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_names(
+    icon_type: *const c_char,
+    out: *mut *mut *mut c_char,
+    len: *mut usize,
+) -> FreeIconsError {
+    let Some(icon_type) = to_str(icon_type) else {
+        return FreeIconsError::InvalidArgument;
+    };
+    match icon_type.parse::<IconType>() {
+        Ok(t) => write_strings(t.names().into_iter().map(String::from).collect(), out, len),
+        Err(e) => e.into(),
+    }
+}
+
+/// release a string returned by the library
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by the library, released only once.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// release an array of strings returned by the library
+///
+/// # Safety
+///
+/// `strings` must be null or an array returned by the library with its
+/// length, released only once.
+#[no_mangle]
+pub unsafe extern "C" fn free_icons_free_strings(strings: *mut *mut c_char, len: usize) {
+    if strings.is_null() {
+        return;
+    }
+    let strings = Box::from_raw(ptr::slice_from_raw_parts_mut(strings, len));
+    for s in strings.iter() {
+        free_icons_free_string(*s);
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+fn to_c_string(s: String) -> *mut c_char {
+    // the strings come from C strings and the icons, so they have no NUL bytes
+    CString::new(s).unwrap_or_default().into_raw()
+}
+
+unsafe fn write_strings(
+    strings: Vec<String>,
+    out: *mut *mut *mut c_char,
+    len: *mut usize,
+) -> FreeIconsError {
+    if out.is_null() || len.is_null() {
+        return FreeIconsError::InvalidArgument;
+    }
+    let strings: Box<[*mut c_char]> = strings.into_iter().map(to_c_string).collect();
+    *len = strings.len();
+    *out = Box::into_raw(strings) as *mut *mut c_char;
+    FreeIconsError::Ok
+}
@@ -0,0 +1,428 @@
+// components for Rust front-end frameworks. They build the virtual DOM of the
+// icon from its parsed SVG, element by element, so that the server and the
+// client render the same nodes and hydration works.
+
+use crate::{document::xml_name, Element, Icon};
+
+/// the root of the icon, with the attributes given as props replacing the
+/// ones of the icon
+fn icon_root(
+    icon: &Icon,
+    class: Option<&str>,
+    size: Option<&str>,
+    fill: Option<&str>,
+    stroke: Option<&str>,
+    stroke_width: Option<&str>,
+) -> Element {
+    let attrs = [
+        ("class", class),
+        ("width", size),
+        ("height", size),
+        ("fill", fill),
+        ("stroke", stroke),
+        ("stroke-width", stroke_width),
+    ];
+    let attrs = attrs.into_iter().filter_map(|(k, v)| Some((k, v?)));
+    match icon.to_document_with(attrs) {
+        Ok(doc) => doc.into_root(),
+        Err(_) => Element::new("svg"),
+    }
+}
+
+/// the names of the elements and attributes as the DOM expects them, e.g.
+/// `viewBox`, since SVG names are case sensitive
+fn names(el: &Element) -> (&str, impl Iterator<Item = (&str, &str)>) {
+    (
+        xml_name(el.name()),
+        el.attrs().map(|(k, v)| (xml_name(k), v)),
+    )
+}
+
+/// `<Icon icon=icon class="h-4" />`. Every prop is reactive: the icon can be
+/// a signal, and so can the attributes.
+#[cfg(feature = "leptos")]
+pub mod leptos {
+    use super::{icon_root, names};
+    use crate::{Element, Node};
+    use leptos::{
+        attr::{
+            any_attribute::{AnyAttribute, IntoAnyAttribute},
+            custom::custom_attribute,
+        },
+        prelude::*,
+        svg,
+    };
+
+    #[component]
+    pub fn Icon(
+        #[prop(into)] icon: Signal<crate::Icon<'static>>,
+        #[prop(into, optional)] class: MaybeProp<String>,
+        /// the width and height of the icon
+        #[prop(into, optional)]
+        size: MaybeProp<String>,
+        #[prop(into, optional)] fill: MaybeProp<String>,
+        #[prop(into, optional)] stroke: MaybeProp<String>,
+        #[prop(into, optional)] stroke_width: MaybeProp<String>,
+    ) -> impl IntoView {
+        move || {
+            let root = icon_root(
+                &icon.get(),
+                class.get().as_deref(),
+                size.get().as_deref(),
+                fill.get().as_deref(),
+                stroke.get().as_deref(),
+                stroke_width.get().as_deref(),
+            );
+            view(&root)
+        }
+    }
+
+    // the SVG elements which leptos can build, the others are left out
+    macro_rules! element {
+        ($name:expr, $attrs:expr, $children:expr, [$($tag:ident),*]) => {
+            match $name {
+                $(stringify!($tag) => svg::$tag()
+                    .add_any_attr($attrs)
+                    .child($children)
+                    .into_any(),)*
+                _ => ().into_any(),
+            }
+        };
+    }
+
+    fn view(el: &Element) -> AnyView {
+        let (name, attrs) = names(el);
+        let attrs: Vec<AnyAttribute> = attrs
+            .map(|(k, v)| custom_attribute(k.to_owned(), v.to_owned()).into_any_attr())
+            .collect();
+        let children: Vec<AnyView> = el
+            .children()
+            .iter()
+            .map(|child| match child {
+                Node::Element(el) => view(el),
+                Node::Text(text) => text.clone().into_any(),
+            })
+            .collect();
+        element!(
+            name,
+            attrs,
+            children,
+            [
+                animate,
+                animateMotion,
+                animateTransform,
+                circle,
+                clipPath,
+                defs,
+                desc,
+                ellipse,
+                g,
+                line,
+                linearGradient,
+                marker,
+                mask,
+                path,
+                pattern,
+                polygon,
+                polyline,
+                radialGradient,
+                rect,
+                set,
+                stop,
+                style,
+                svg,
+                symbol,
+                text,
+                textPath,
+                title,
+                tspan
+            ]
+        )
+    }
+}
+
+/// `<Icon icon={icon} class="h-4" />`. The icon renders again when its props
+/// change.
+#[cfg(feature = "yew")]
+pub mod yew {
+    use super::{icon_root, names};
+    use crate::{Element, Node};
+    use yew::{
+        prelude::*,
+        virtual_dom::{ApplyAttributeAs, VNode, VTag, VText},
+    };
+
+    #[derive(Debug, Clone, PartialEq, Properties)]
+    pub struct IconProps {
+        pub icon: crate::Icon<'static>,
+        #[prop_or_default]
+        pub class: Option<AttrValue>,
+        /// the width and height of the icon
+        #[prop_or_default]
+        pub size: Option<AttrValue>,
+        #[prop_or_default]
+        pub fill: Option<AttrValue>,
+        #[prop_or_default]
+        pub stroke: Option<AttrValue>,
+        #[prop_or_default]
+        pub stroke_width: Option<AttrValue>,
+    }
+
+    #[function_component]
+    pub fn Icon(props: &IconProps) -> Html {
+        let root = icon_root(
+            &props.icon,
+            props.class.as_deref(),
+            props.size.as_deref(),
+            props.fill.as_deref(),
+            props.stroke.as_deref(),
+            props.stroke_width.as_deref(),
+        );
+        vnode(&root)
+    }
+
+    pub(super) fn vnode(el: &Element) -> VNode {
+        let (name, attrs) = names(el);
+        let mut tag = VTag::new(name.to_owned());
+        let map = tag.attributes.get_mut_index_map();
+        for (k, v) in attrs {
+            let value = (AttrValue::from(v.to_owned()), ApplyAttributeAs::Attribute);
+            map.insert(AttrValue::from(k.to_owned()), value);
+        }
+        for child in el.children() {
+            tag.add_child(match child {
+                Node::Element(el) => vnode(el),
+                Node::Text(text) => VText::new(text.clone()).into(),
+            });
+        }
+        tag.into()
+    }
+}
+
+/// `Icon { icon: icon, class: "h-4" }`. The icon renders again when its props
+/// change.
+#[cfg(feature = "dioxus")]
+pub mod dioxus {
+    use super::{icon_root, names};
+    use crate::Node;
+    use dioxus::{
+        core::{Attribute, DynamicNode, Template, TemplateAttribute, TemplateNode, VNode, VText},
+        prelude::*,
+    };
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::{Mutex, OnceLock},
+    };
+
+    const SVG_NS: &str = "http://www.w3.org/2000/svg";
+
+    const TEXT: Template = Template {
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[&[0]],
+        attr_paths: &[],
+    };
+
+    #[component]
+    pub fn Icon(
+        icon: crate::Icon<'static>,
+        #[props(into)] class: Option<String>,
+        /// the width and height of the icon
+        #[props(into)]
+        size: Option<String>,
+        #[props(into)] fill: Option<String>,
+        #[props(into)] stroke: Option<String>,
+        #[props(into)] stroke_width: Option<String>,
+    ) -> Element {
+        let root = icon_root(
+            &icon,
+            class.as_deref(),
+            size.as_deref(),
+            fill.as_deref(),
+            stroke.as_deref(),
+            stroke_width.as_deref(),
+        );
+        Ok(vnode(&root))
+    }
+
+    /// an element is a template with the tag, whose attributes and children
+    /// are dynamic
+    pub(super) fn vnode(el: &crate::Element) -> VNode {
+        let (name, attrs) = names(el);
+        let attrs: Box<[Attribute]> = attrs
+            .map(|(k, v)| Attribute::new(intern(k), v.to_owned(), None, false))
+            .collect();
+        let children: Vec<_> = el
+            .children()
+            .iter()
+            .map(|child| match child {
+                Node::Element(el) => vnode(el),
+                Node::Text(text) => VNode::new(
+                    None,
+                    TEXT,
+                    Box::new([DynamicNode::Text(VText::new(text))]),
+                    Box::new([]),
+                ),
+            })
+            .collect();
+        let nodes: Box<[DynamicNode]> = match children.is_empty() {
+            true => Box::new([]),
+            false => Box::new([DynamicNode::Fragment(children)]),
+        };
+        let template = template(intern(name), !nodes.is_empty());
+        VNode::new(None, template, nodes, Box::new([attrs]))
+    }
+
+    /// the templates are static, so they are built once per tag
+    fn template(tag: &'static str, children: bool) -> Template {
+        static TEMPLATES: OnceLock<Mutex<HashMap<(&str, bool), Template>>> = OnceLock::new();
+        let mut templates = TEMPLATES.get_or_init(Default::default).lock().unwrap();
+        *templates.entry((tag, children)).or_insert_with(|| {
+            let roots = vec![TemplateNode::Element {
+                tag,
+                namespace: Some(SVG_NS),
+                attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+                children: match children {
+                    true => &[TemplateNode::Dynamic { id: 0 }],
+                    false => &[],
+                },
+            }];
+            Template {
+                roots: roots.leak(),
+                node_paths: match children {
+                    true => &[&[0, 0]],
+                    false => &[],
+                },
+                attr_paths: &[&[0]],
+            }
+        })
+    }
+
+    /// the names of the elements and attributes must be static. The icons only
+    /// use a few of them, so they are leaked once each.
+    fn intern(name: &str) -> &'static str {
+        static NAMES: OnceLock<Mutex<HashSet<&str>>> = OnceLock::new();
+        let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+        match names.get(name) {
+            Some(name) => name,
+            None => {
+                let name = String::leak(name.to_owned());
+                names.insert(name);
+                name
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn icon_root_should_replace_attributes() {
+        let icon: Icon = "heroicons:outline:trash".parse().unwrap();
+        let root = icon_root(&icon, Some("h-4"), Some("16"), None, None, Some("2"));
+        assert_eq!(root.attr("class"), Some("h-4"));
+        assert_eq!(root.attr("width"), Some("16"));
+        assert_eq!(root.attr("stroke-width"), Some("2"));
+        assert_eq!(root.attr("fill"), Some("none"));
+        let (name, mut attrs) = names(&root);
+        assert_eq!(name, "svg");
+        assert!(attrs.any(|(k, _)| k == "viewBox"));
+    }
+
+    #[cfg(feature = "feather")]
+    #[test]
+    fn icon_root_should_keep_the_attributes_of_the_icon() {
+        let icon: Icon = "feather:activity".parse().unwrap();
+        let icon = icon.stroke_color("red").stroke_width("3");
+        let root = icon_root(&icon, Some("h-4"), None, None, None, Some("4"));
+        for name in ["stroke", "stroke-width", "class"] {
+            assert_eq!(root.attrs().filter(|(k, _)| *k == name).count(), 1);
+        }
+        assert_eq!(root.attr("stroke"), Some("red"));
+        assert_eq!(root.attr("stroke-width"), Some("4"));
+    }
+
+    #[cfg(all(feature = "leptos", feature = "heroicons"))]
+    #[test]
+    fn leptos_icon_should_work() {
+        use ::leptos::prelude::*;
+
+        let owner = Owner::new();
+        let html = owner.with(|| {
+            let icon: Icon = "heroicons:outline:trash".parse().unwrap();
+            let icon = icon.stroke_color("red");
+            view! { <leptos::Icon icon=icon class="h-4" stroke_width="2" /> }.to_html()
+        });
+        assert!(html.starts_with("<svg "));
+        assert!(html.ends_with("</svg>"));
+        assert!(html.contains(" viewBox=\"0 0 24 24\""));
+        assert!(html.contains(" class=\"h-4\""));
+        assert_eq!(html.matches(" stroke=").count(), 1);
+        assert!(html.contains(" stroke=\"red\""));
+        assert_eq!(html.matches(" stroke-width=").count(), 1);
+        assert!(html.contains(" stroke-width=\"2\""));
+        assert!(html.contains("<path "));
+    }
+
+    #[cfg(all(feature = "yew", feature = "heroicons"))]
+    #[test]
+    fn yew_icon_should_work() {
+        use ::yew::virtual_dom::VNode;
+
+        let icon: Icon = "heroicons:outline:trash".parse().unwrap();
+        let root = icon_root(&icon, Some("h-4"), None, None, None, None);
+        let VNode::VTag(tag) = yew::vnode(&root) else {
+            panic!("expected an element");
+        };
+        assert_eq!(tag.tag(), "svg");
+        let attrs: Vec<_> = tag.attributes.iter().collect();
+        assert!(attrs.contains(&("class", "h-4")));
+        assert!(attrs.contains(&("viewBox", "0 0 24 24")));
+        assert!(tag.children().is_some());
+
+        // the DOM keeps the last of duplicate attributes, which would drop the
+        // props of the icon
+        let icon = icon.stroke_color("red");
+        let root = icon_root(&icon, None, None, None, None, Some("2"));
+        let VNode::VTag(tag) = yew::vnode(&root) else {
+            panic!("expected an element");
+        };
+        let attrs: Vec<_> = tag.attributes.iter().collect();
+        assert!(attrs.contains(&("stroke", "red")));
+        assert!(attrs.contains(&("stroke-width", "2")));
+    }
+
+    #[cfg(all(feature = "dioxus", feature = "heroicons"))]
+    #[test]
+    fn dioxus_icon_should_work() {
+        use ::dioxus::core::{AttributeValue, DynamicNode, TemplateNode};
+
+        let icon: Icon = "heroicons:outline:trash".parse().unwrap();
+        let root = icon_root(&icon, Some("h-4"), None, None, None, None);
+        let node = dioxus::vnode(&root);
+        let TemplateNode::Element { tag, namespace, .. } = node.template.roots[0] else {
+            panic!("expected an element");
+        };
+        assert_eq!(
+            (tag, namespace),
+            ("svg", Some("http://www.w3.org/2000/svg"))
+        );
+        let attrs: Vec<_> = node.dynamic_attrs[0]
+            .iter()
+            .map(|attr| (attr.name, attr.value.clone()))
+            .collect();
+        assert!(attrs.contains(&("class", AttributeValue::Text("h-4".into()))));
+        assert!(attrs.contains(&("viewBox", AttributeValue::Text("0 0 24 24".into()))));
+        let DynamicNode::Fragment(children) = &node.dynamic_nodes[0] else {
+            panic!("expected the children");
+        };
+        // the templates are shared by the elements with the same tag
+        let path = dioxus::vnode(&root).dynamic_nodes[0].clone();
+        let DynamicNode::Fragment(again) = path else {
+            unreachable!()
+        };
+        assert_eq!(children[0].template, again[0].template);
+    }
+}
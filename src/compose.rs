@@ -0,0 +1,295 @@
+use crate::{next_id_prefix, Element, IconDocument, IconError, ViewBox};
+
+/// root attributes of an icon which make no sense once it is embedded into another SVG
+pub(crate) const DROPPED_ATTRS: &[&str] = &[
+    "xmlns",
+    "xmlns:xlink",
+    "version",
+    "xml:space",
+    "x",
+    "y",
+    "width",
+    "height",
+    "id",
+    "class",
+    "style",
+];
+
+/// merge a base icon and overlay icons (badges, status dots, locks...) into
+/// a single SVG
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition<'a> {
+    base: &'a str,
+    overlays: Vec<Overlay<'a>>,
+    id_prefix: Option<&'a str>,
+}
+
+/// an icon drawn on top of the base icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlay<'a> {
+    svg: &'a str,
+    position: Position,
+    scale: f32,
+    knockout: Option<f32>,
+}
+
+/// where an overlay is placed on the base icon
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Position {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+    /// the center of the overlay, as fractions of the base width and height
+    At(f32, f32),
+}
+
+impl<'a> Composition<'a> {
+    pub fn new(base: &'a str) -> Self {
+        Self {
+            base,
+            overlays: Vec::new(),
+            id_prefix: None,
+        }
+    }
+
+    pub fn overlay(mut self, overlay: Overlay<'a>) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// the prefix of the ids in the merged icon, a unique one is generated if
+    /// not set
+    pub fn id_prefix(mut self, prefix: &'a str) -> Self {
+        self.id_prefix = Some(prefix);
+        self
+    }
+
+    pub fn render(&self) -> Result<IconDocument, IconError> {
+        let prefix = match self.id_prefix {
+            Some(prefix) => prefix.to_owned(),
+            None => next_id_prefix(),
+        };
+
+        let mut base = IconDocument::parse(self.base)?;
+        base.rewrite_ids(&format!("{prefix}-b"));
+        let base_box = base.view_box().ok_or(IconError::Parse {
+            pos: 0,
+            reason: "base icon has no viewBox",
+        })?;
+
+        let mut bounds = base_box;
+        let mut mask = Element::new("mask");
+        let mut layers = Vec::with_capacity(self.overlays.len());
+        for (i, overlay) in self.overlays.iter().enumerate() {
+            let mut doc = IconDocument::parse(overlay.svg)?;
+            doc.rewrite_ids(&format!("{prefix}-o{i}"));
+            let view_box = doc.view_box().ok_or(IconError::Parse {
+                pos: 0,
+                reason: "overlay icon has no viewBox",
+            })?;
+
+            let size = base_box.width.min(base_box.height) * overlay.scale;
+            let (x, y) = overlay.position.origin(&base_box, size);
+            bounds = union(bounds, x, y, size);
+
+            if let Some(gap) = overlay.knockout {
+                let mut circle = Element::new("circle");
+                circle
+                    .set_attr("cx", num(x + size / 2.0))
+                    .set_attr("cy", num(y + size / 2.0))
+                    .set_attr("r", num(size / 2.0 + gap))
+                    .set_attr("fill", "#000");
+                mask.push(circle);
+            }
+
+            let mut root = doc.into_root();
+            let mut layer = Element::new("svg");
+            layer
+                .set_attr("x", num(x))
+                .set_attr("y", num(y))
+                .set_attr("width", num(size))
+                .set_attr("height", num(size))
+                .set_attr("viewbox", view_box.to_string());
+            for (k, v) in root.attrs() {
+                if !DROPPED_ATTRS.contains(&k) && !k.eq_ignore_ascii_case("viewbox") {
+                    layer.set_attr(k, v);
+                }
+            }
+            // don't inherit the paint of the base icon
+            if layer.attr("fill").is_none() {
+                layer.set_attr("fill", "currentColor");
+            }
+            if layer.attr("stroke").is_none() {
+                layer.set_attr("stroke", "none");
+            }
+            layer.children_mut().append(root.children_mut());
+            layers.push(layer);
+        }
+
+        let mut root = base.into_root();
+        if !mask.children().is_empty() {
+            let mask_id = format!("{prefix}-k");
+            let mut rect = Element::new("rect");
+            rect.set_attr("x", num(bounds.min_x))
+                .set_attr("y", num(bounds.min_y))
+                .set_attr("width", num(bounds.width))
+                .set_attr("height", num(bounds.height))
+                .set_attr("fill", "#fff");
+            mask.children_mut().insert(0, rect.into());
+            mask.set_attr("id", mask_id.as_str())
+                .set_attr("maskUnits", "userSpaceOnUse")
+                .set_attr("x", num(bounds.min_x))
+                .set_attr("y", num(bounds.min_y))
+                .set_attr("width", num(bounds.width))
+                .set_attr("height", num(bounds.height));
+
+            let mut defs = Element::new("defs");
+            defs.push(mask);
+            let mut group = Element::new("g");
+            group.set_attr("mask", format!("url(#{mask_id})"));
+            group.children_mut().append(root.children_mut());
+            root.push(defs).push(group);
+        }
+        for layer in layers {
+            root.push(layer);
+        }
+
+        if bounds != base_box {
+            resize(&mut root, &base_box, &bounds);
+        }
+
+        Ok(IconDocument::new(root))
+    }
+}
+
+impl<'a> Overlay<'a> {
+    /// an overlay in the bottom right corner, at 50% of the base icon size
+    pub fn new(svg: &'a str) -> Self {
+        Self {
+            svg,
+            position: Position::default(),
+            scale: 0.5,
+            knockout: None,
+        }
+    }
+
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// the size of the overlay relative to the base icon
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// cut a ring of the given width (in base icon units) around the overlay
+    /// out of the base icon, so that the overlay stands out
+    pub fn knockout(mut self, gap: f32) -> Self {
+        self.knockout = Some(gap);
+        self
+    }
+}
+
+impl Position {
+    /// the top left corner of an overlay of the given size
+    fn origin(&self, base: &ViewBox, size: f32) -> (f32, f32) {
+        let (fx, fy) = match self {
+            Position::TopLeft => return (base.min_x, base.min_y),
+            Position::TopRight => return (base.min_x + base.width - size, base.min_y),
+            Position::BottomLeft => return (base.min_x, base.min_y + base.height - size),
+            Position::BottomRight => {
+                return (
+                    base.min_x + base.width - size,
+                    base.min_y + base.height - size,
+                )
+            }
+            Position::Center => (0.5, 0.5),
+            Position::At(x, y) => (*x, *y),
+        };
+        (
+            base.min_x + base.width * fx - size / 2.0,
+            base.min_y + base.height * fy - size / 2.0,
+        )
+    }
+}
+
+fn union(b: ViewBox, x: f32, y: f32, size: f32) -> ViewBox {
+    let min_x = b.min_x.min(x);
+    let min_y = b.min_y.min(y);
+    let max_x = (b.min_x + b.width).max(x + size);
+    let max_y = (b.min_y + b.height).max(y + size);
+    ViewBox {
+        min_x,
+        min_y,
+        width: max_x - min_x,
+        height: max_y - min_y,
+    }
+}
+
+/// grow the viewBox to fit the overlays, keeping the scale of the base icon
+fn resize(root: &mut Element, base: &ViewBox, bounds: &ViewBox) {
+    for (name, ratio) in [
+        ("width", bounds.width / base.width),
+        ("height", bounds.height / base.height),
+    ] {
+        if let Some(value) = root.attr(name).and_then(|v| v.parse::<f32>().ok()) {
+            root.set_attr(name, num(value * ratio));
+        }
+    }
+    let name = if root.attr("viewBox").is_some() {
+        "viewBox"
+    } else {
+        "viewbox"
+    };
+    root.set_attr(name, bounds.to_string());
+}
+
+/// format a coordinate without float noise
+pub(crate) fn num(v: f32) -> String {
+    // adding 0 turns -0 into 0
+    let v = (v * 1000.0).round() / 1000.0 + 0.0;
+    v.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BELL: &str = r#"<svg viewbox="0 0 24 24" fill=none stroke=currentColor xmlns=http://www.w3.org/2000/svg><path d="M12 3v18"/></svg>"#;
+    const DOT: &str = r#"<svg viewbox="0 0 16 16" id=dot xmlns=http://www.w3.org/2000/svg><circle cx=8 cy=8 r=8 /></svg>"#;
+
+    #[test]
+    fn composition_should_merge_overlays() {
+        let doc = Composition::new(BELL)
+            .overlay(Overlay::new(DOT).scale(0.25).knockout(1.5))
+            .id_prefix("c")
+            .render()
+            .unwrap();
+        assert_eq!(
+            doc.to_string(),
+            r##"<svg viewbox="0 0 24 24" fill=none stroke=currentColor xmlns=http://www.w3.org/2000/svg><defs><mask id=c-k maskUnits=userSpaceOnUse x=0 y=0 width=24 height=24><rect x=0 y=0 width=24 height=24 fill=#fff /><circle cx=21 cy=21 r=4.5 fill=#000 /></mask></defs><g mask=url(#c-k)><path d="M12 3v18"/></g><svg x=18 y=18 width=6 height=6 viewbox="0 0 16 16" fill=currentColor stroke=none><circle cx=8 cy=8 r=8 /></svg></svg>"##
+        );
+    }
+
+    #[test]
+    fn composition_should_grow_view_box() {
+        let doc = Composition::new(BELL)
+            .overlay(Overlay::new(DOT).position(Position::At(1.0, 0.0)))
+            .render()
+            .unwrap();
+        assert_eq!(
+            doc.view_box(),
+            Some(ViewBox {
+                min_x: 0.0,
+                min_y: -6.0,
+                width: 30.0,
+                height: 30.0
+            })
+        );
+    }
+}
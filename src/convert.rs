@@ -0,0 +1,836 @@
+use crate::{compose::num, Element, Icon, IconDocument, IconError, ViewBox};
+use std::fmt::Write;
+
+/// an icon converted to another vector format
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Converted {
+    pub output: String,
+    /// what could not be converted and was left out, e.g. `mask` or
+    /// `path: stroke-dasharray`
+    pub unsupported: Vec<String>,
+}
+
+/// a shape, with its outline in the coordinates of the viewBox moved to 0,0
+#[derive(Debug)]
+struct Shape<'a> {
+    name: &'a str,
+    data: String,
+    fill: Option<Color>,
+    even_odd: bool,
+    stroke: Option<Stroke>,
+}
+
+#[derive(Debug)]
+struct Stroke {
+    color: Color,
+    width: f32,
+    cap: &'static str,
+    join: &'static str,
+    miter_limit: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Color {
+    rgb: [u8; 3],
+    alpha: f32,
+}
+
+/// the presentation attributes inherited from the ancestors
+#[derive(Debug, Clone)]
+struct Paint<'a> {
+    fill: &'a str,
+    stroke: &'a str,
+    stroke_width: f32,
+    cap: &'a str,
+    join: &'a str,
+    miter_limit: f32,
+    fill_rule: &'a str,
+    fill_opacity: f32,
+    stroke_opacity: f32,
+    opacity: f32,
+    transform: Matrix,
+}
+
+/// an affine transform `[a b c d e f]`, as in `matrix(a b c d e f)`
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// elements which don't draw anything
+const IGNORED: &[&str] = &["title", "desc", "metadata"];
+
+impl IconDocument {
+    /// convert to an Android `VectorDrawable`, 24dp wide or high. `currentColor`
+    /// is painted with the color, e.g. `#000000`.
+    pub fn to_vector_drawable(&self, color: &str) -> Converted {
+        let (view_box, shapes, unsupported) = self.shapes(color);
+        let scale = 24.0 / view_box.width.max(view_box.height);
+        let mut out = format!(
+            "<vector xmlns:android=\"http://schemas.android.com/apk/res/android\"\n    android:width=\"{}dp\"\n    android:height=\"{}dp\"\n    android:viewportWidth=\"{}\"\n    android:viewportHeight=\"{}\">\n",
+            num(view_box.width * scale),
+            num(view_box.height * scale),
+            num(view_box.width),
+            num(view_box.height)
+        );
+        for shape in shapes {
+            if shape.fill.is_none() && shape.stroke.is_none() {
+                continue;
+            }
+            out.push_str("    <path\n");
+            if let Some(fill) = shape.fill {
+                let _ = writeln!(out, "        android:fillColor=\"{fill}\"");
+                if shape.even_odd {
+                    out.push_str("        android:fillType=\"evenOdd\"\n");
+                }
+            }
+            if let Some(stroke) = &shape.stroke {
+                let _ = write!(
+                    out,
+                    "        android:strokeColor=\"{}\"\n        android:strokeWidth=\"{}\"\n",
+                    stroke.color,
+                    num(stroke.width)
+                );
+                if stroke.cap != "butt" {
+                    let _ = writeln!(out, "        android:strokeLineCap=\"{}\"", stroke.cap);
+                }
+                if stroke.join != "miter" {
+                    let _ = writeln!(out, "        android:strokeLineJoin=\"{}\"", stroke.join);
+                } else if stroke.miter_limit != 4.0 {
+                    let _ = writeln!(
+                        out,
+                        "        android:strokeMiterLimit=\"{}\"",
+                        num(stroke.miter_limit)
+                    );
+                }
+            }
+            let _ = writeln!(out, "        android:pathData=\"{}\"/>", shape.data);
+        }
+        out.push_str("</vector>\n");
+        Converted {
+            output: out,
+            unsupported,
+        }
+    }
+
+    /// convert to a WPF `DrawingImage`. `currentColor` is painted with the
+    /// color, e.g. `#000000`.
+    pub fn to_xaml_drawing_image(&self, color: &str) -> Converted {
+        let (view_box, shapes, unsupported) = self.shapes(color);
+        let mut out = format!(
+            "<DrawingImage xmlns=\"http://schemas.microsoft.com/winfx/2006/xaml/presentation\">\n  <DrawingImage.Drawing>\n    <DrawingGroup ClipGeometry=\"M0,0 H{} V{} H0 Z\">\n",
+            num(view_box.width),
+            num(view_box.height)
+        );
+        for shape in shapes {
+            if shape.fill.is_none() && shape.stroke.is_none() {
+                continue;
+            }
+            out.push_str("      <GeometryDrawing");
+            if let Some(fill) = shape.fill {
+                let _ = write!(out, " Brush=\"{fill}\"");
+            }
+            let rule = if shape.even_odd { "F0" } else { "F1" };
+            let _ = write!(out, " Geometry=\"{rule} {}\"", shape.data);
+            let Some(stroke) = &shape.stroke else {
+                out.push_str(" />\n");
+                continue;
+            };
+            let cap = match stroke.cap {
+                "round" => "Round",
+                "square" => "Square",
+                _ => "Flat",
+            };
+            let join = match stroke.join {
+                "round" => "Round",
+                "bevel" => "Bevel",
+                _ => "Miter",
+            };
+            let _ = write!(
+                out,
+                ">\n        <GeometryDrawing.Pen>\n          <Pen Brush=\"{}\" Thickness=\"{}\" StartLineCap=\"{cap}\" EndLineCap=\"{cap}\" LineJoin=\"{join}\" MiterLimit=\"{}\" />\n        </GeometryDrawing.Pen>\n      </GeometryDrawing>\n",
+                stroke.color,
+                num(stroke.width),
+                num(stroke.miter_limit)
+            );
+        }
+        out.push_str("    </DrawingGroup>\n  </DrawingImage.Drawing>\n</DrawingImage>\n");
+        Converted {
+            output: out,
+            unsupported,
+        }
+    }
+
+    /// convert the filled shapes to a single XAML `PathGeometry`, for WPF or
+    /// WinUI. Strokes and colors can't be expressed by a geometry, they are
+    /// reported as unsupported.
+    pub fn to_xaml_path_geometry(&self) -> Converted {
+        let (_, shapes, mut unsupported) = self.shapes("#000");
+        let mut figures = Vec::new();
+        let mut even_odd = None;
+        for shape in &shapes {
+            if shape.stroke.is_some() {
+                dedup_push(&mut unsupported, Some(format!("{}: stroke", shape.name)));
+            }
+            if shape.fill.is_none() {
+                continue;
+            }
+            if *even_odd.get_or_insert(shape.even_odd) != shape.even_odd {
+                dedup_push(&mut unsupported, Some(format!("{}: fill-rule", shape.name)));
+            }
+            figures.push(shape.data.as_str());
+        }
+        let rule = match even_odd {
+            Some(true) => "EvenOdd",
+            _ => "Nonzero",
+        };
+        Converted {
+            output: format!(
+                "<PathGeometry xmlns=\"http://schemas.microsoft.com/winfx/2006/xaml/presentation\" FillRule=\"{rule}\" Figures=\"{}\" />\n",
+                figures.join(" ")
+            ),
+            unsupported,
+        }
+    }
+
+    fn shapes(&self, color: &str) -> (ViewBox, Vec<Shape<'_>>, Vec<String>) {
+        let mut unsupported = Vec::new();
+        let current = Color::parse(color, None).unwrap_or_else(|| {
+            unsupported.push(format!("color: {color}"));
+            Color::BLACK
+        });
+        let view_box = self.view_box().unwrap_or(ViewBox {
+            min_x: 0.0,
+            min_y: 0.0,
+            width: 24.0,
+            height: 24.0,
+        });
+        let paint = Paint {
+            fill: "black",
+            stroke: "none",
+            stroke_width: 1.0,
+            cap: "butt",
+            join: "miter",
+            miter_limit: 4.0,
+            fill_rule: "nonzero",
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            opacity: 1.0,
+            transform: [1.0, 0.0, 0.0, 1.0, -view_box.min_x, -view_box.min_y],
+        };
+        let paint = inherit(self.root(), &paint, &mut unsupported);
+        let mut shapes = Vec::new();
+        for el in self.root().elements() {
+            collect(el, &paint, current, &mut shapes, &mut unsupported);
+        }
+        (view_box, shapes, unsupported)
+    }
+}
+
+impl Icon<'_> {
+    /// see `IconDocument::to_vector_drawable`
+    pub fn to_vector_drawable(&self, color: &str) -> Result<Converted, IconError> {
+        Ok(self.to_document()?.to_vector_drawable(color))
+    }
+
+    /// see `IconDocument::to_xaml_drawing_image`
+    pub fn to_xaml_drawing_image(&self, color: &str) -> Result<Converted, IconError> {
+        Ok(self.to_document()?.to_xaml_drawing_image(color))
+    }
+
+    /// see `IconDocument::to_xaml_path_geometry`
+    pub fn to_xaml_path_geometry(&self) -> Result<Converted, IconError> {
+        Ok(self.to_document()?.to_xaml_path_geometry())
+    }
+}
+
+fn collect<'a>(
+    el: &'a Element,
+    parent: &Paint<'a>,
+    current: Color,
+    shapes: &mut Vec<Shape<'a>>,
+    unsupported: &mut Vec<String>,
+) {
+    let name = el.name();
+    if IGNORED.contains(&name) {
+        return;
+    }
+    if !matches!(
+        name,
+        "g" | "path" | "circle" | "ellipse" | "rect" | "line" | "polyline" | "polygon"
+    ) {
+        dedup_push(unsupported, Some(name.to_owned()));
+        return;
+    }
+    let paint = inherit(el, parent, unsupported);
+
+    let data = match name {
+        "g" => {
+            for child in el.elements() {
+                collect(child, &paint, current, shapes, unsupported);
+            }
+            return;
+        }
+        _ => shape_data(el),
+    };
+    let Some(data) = data.and_then(|d| path_data(&d, &paint.transform)) else {
+        dedup_push(unsupported, Some(format!("{name}: invalid geometry")));
+        return;
+    };
+
+    let mut paint_color = |value: &str, attr: &str, opacity: f32| {
+        if value == "none" {
+            return None;
+        }
+        let color = Color::parse(value, Some(current)).unwrap_or_else(|| {
+            dedup_push(unsupported, Some(format!("{name}: {attr} {value}")));
+            current
+        });
+        Some(Color {
+            alpha: color.alpha * opacity * paint.opacity,
+            ..color
+        })
+    };
+    let fill = paint_color(paint.fill, "fill", paint.fill_opacity);
+    let stroke = paint_color(paint.stroke, "stroke", paint.stroke_opacity).map(|color| Stroke {
+        color,
+        width: paint.stroke_width * scale_of(&paint.transform),
+        cap: match paint.cap {
+            "round" => "round",
+            "square" => "square",
+            _ => "butt",
+        },
+        join: match paint.join {
+            "round" => "round",
+            "bevel" => "bevel",
+            _ => "miter",
+        },
+        miter_limit: paint.miter_limit,
+    });
+    shapes.push(Shape {
+        name,
+        data,
+        fill,
+        even_odd: paint.fill_rule == "evenodd",
+        stroke,
+    });
+}
+
+/// the paint of the element, with the properties it inherits from its parent
+fn inherit<'a>(el: &'a Element, parent: &Paint<'a>, unsupported: &mut Vec<String>) -> Paint<'a> {
+    let name = el.name();
+    for attr in ["clip-path", "mask", "filter"] {
+        if property(el, attr).is_some_and(|v| v != "none") {
+            dedup_push(unsupported, Some(format!("{name}: {attr}")));
+        }
+    }
+    if property(el, "stroke-dasharray").is_some_and(|v| v != "none") {
+        dedup_push(unsupported, Some(format!("{name}: stroke-dasharray")));
+    }
+
+    let mut paint = parent.clone();
+    let number = |attr: &str, default: f32| {
+        property(el, attr)
+            .and_then(|v| v.trim_end_matches("px").parse().ok())
+            .unwrap_or(default)
+    };
+    paint.fill = property(el, "fill").unwrap_or(paint.fill);
+    paint.stroke = property(el, "stroke").unwrap_or(paint.stroke);
+    paint.stroke_width = number("stroke-width", paint.stroke_width);
+    paint.cap = property(el, "stroke-linecap").unwrap_or(paint.cap);
+    paint.join = property(el, "stroke-linejoin").unwrap_or(paint.join);
+    paint.miter_limit = number("stroke-miterlimit", paint.miter_limit);
+    paint.fill_rule = property(el, "fill-rule").unwrap_or(paint.fill_rule);
+    paint.fill_opacity = number("fill-opacity", paint.fill_opacity);
+    paint.stroke_opacity = number("stroke-opacity", paint.stroke_opacity);
+    paint.opacity *= number("opacity", 1.0);
+    if let Some(transform) = property(el, "transform") {
+        match parse_transform(transform) {
+            Some(m) => paint.transform = multiply(&paint.transform, &m),
+            None => dedup_push(unsupported, Some(format!("{name}: transform {transform}"))),
+        }
+    }
+    paint
+}
+
+/// a presentation attribute, or the same property in the `style` attribute
+fn property<'a>(el: &'a Element, name: &str) -> Option<&'a str> {
+    let style = el.attr("style").and_then(|style| {
+        style.split(';').find_map(|decl| {
+            let (k, v) = decl.split_once(':')?;
+            (k.trim() == name).then(|| v.trim())
+        })
+    });
+    style.or_else(|| el.attr(name)).map(str::trim)
+}
+
+/// the outline of a basic shape as path data
+fn shape_data(el: &Element) -> Option<String> {
+    let attr = |name: &str| -> Option<f32> {
+        el.attr(name)
+            .map_or(Some(0.0), |v| v.trim_end_matches("px").parse().ok())
+    };
+    let points = || -> Option<Vec<String>> {
+        let values = el
+            .attr("points")?
+            .split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(
+            values
+                .chunks_exact(2)
+                .map(|p| format!("{} {}", num(p[0]), num(p[1])))
+                .collect(),
+        )
+    };
+
+    Some(match el.name() {
+        "path" => el.attr("d")?.to_owned(),
+        "line" => format!(
+            "M{} {}L{} {}",
+            num(attr("x1")?),
+            num(attr("y1")?),
+            num(attr("x2")?),
+            num(attr("y2")?)
+        ),
+        "polyline" => format!("M{}", points()?.join("L")),
+        "polygon" => format!("M{}Z", points()?.join("L")),
+        "circle" => ellipse(attr("cx")?, attr("cy")?, attr("r")?, attr("r")?),
+        "ellipse" => ellipse(attr("cx")?, attr("cy")?, attr("rx")?, attr("ry")?),
+        "rect" => {
+            let (x, y, w, h) = (attr("x")?, attr("y")?, attr("width")?, attr("height")?);
+            let (rx, ry) = match (el.attr("rx"), el.attr("ry")) {
+                (None, None) => (0.0, 0.0),
+                (Some(_), None) => (attr("rx")?, attr("rx")?),
+                (None, Some(_)) => (attr("ry")?, attr("ry")?),
+                _ => (attr("rx")?, attr("ry")?),
+            };
+            let (rx, ry) = (rx.min(w / 2.0), ry.min(h / 2.0));
+            if rx <= 0.0 || ry <= 0.0 {
+                format!(
+                    "M{} {}H{}V{}H{}Z",
+                    num(x),
+                    num(y),
+                    num(x + w),
+                    num(y + h),
+                    num(x)
+                )
+            } else {
+                let arc = |x: f32, y: f32| {
+                    format!("A{} {} 0 0 1 {} {}", num(rx), num(ry), num(x), num(y))
+                };
+                format!(
+                    "M{} {}H{}{}V{}{}H{}{}V{}{}Z",
+                    num(x + rx),
+                    num(y),
+                    num(x + w - rx),
+                    arc(x + w, y + ry),
+                    num(y + h - ry),
+                    arc(x + w - rx, y + h),
+                    num(x + rx),
+                    arc(x, y + h - ry),
+                    num(y + ry),
+                    arc(x + rx, y),
+                )
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> String {
+    let (rx_s, ry_s) = (num(rx), num(ry));
+    format!(
+        "M{} {}A{rx_s} {ry_s} 0 1 0 {} {}A{rx_s} {ry_s} 0 1 0 {} {}Z",
+        num(cx - rx),
+        num(cy),
+        num(cx + rx),
+        num(cy),
+        num(cx - rx),
+        num(cy)
+    )
+}
+
+/// normalize path data so that every segment has an explicit command and the
+/// arc flags are separated, as Android and XAML can't parse the compact forms.
+/// Transformed paths are converted to absolute coordinates.
+fn path_data(d: &str, transform: &Matrix) -> Option<String> {
+    let segments = parse_path(d)?;
+    let segments = if *transform == IDENTITY {
+        segments
+    } else {
+        transform_path(&segments, transform)?
+    };
+    let mut out = String::with_capacity(d.len());
+    for (cmd, args) in segments {
+        out.push(cmd as char);
+        let args = args.iter().map(|v| num(*v)).collect::<Vec<_>>();
+        out.push_str(&args.join(" "));
+    }
+    Some(out)
+}
+
+fn arity(cmd: u8) -> Option<usize> {
+    Some(match cmd.to_ascii_uppercase() {
+        b'Z' => 0,
+        b'H' | b'V' => 1,
+        b'M' | b'L' | b'T' => 2,
+        b'S' | b'Q' => 4,
+        b'C' => 6,
+        b'A' => 7,
+        _ => return None,
+    })
+}
+
+/// parse SVG path data into segments with an explicit command each
+fn parse_path(d: &str) -> Option<Vec<(u8, Vec<f32>)>> {
+    let bytes = d.as_bytes();
+    let mut pos = 0;
+    let mut segments = Vec::new();
+    let mut cmd = None;
+    loop {
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b',') {
+            pos += 1;
+        }
+        if pos == bytes.len() {
+            return Some(segments);
+        }
+        let c = bytes[pos];
+        let current = if c.is_ascii_alphabetic() {
+            pos += 1;
+            arity(c)?;
+            c
+        } else {
+            // an implicit repetition of the previous command, moves repeat
+            // as lines
+            match cmd? {
+                b'M' => b'L',
+                b'm' => b'l',
+                b'Z' | b'z' => return None,
+                c => c,
+            }
+        };
+        cmd = Some(current);
+
+        let mut args = Vec::with_capacity(7);
+        for i in 0..arity(current)? {
+            while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b',') {
+                pos += 1;
+            }
+            let is_flag = current.eq_ignore_ascii_case(&b'A') && (i == 3 || i == 4);
+            let start = pos;
+            if is_flag {
+                match bytes.get(pos) {
+                    Some(b'0') | Some(b'1') => pos += 1,
+                    _ => return None,
+                }
+            } else {
+                pos = scan_number(bytes, pos);
+            }
+            args.push(d.get(start..pos)?.parse().ok()?);
+        }
+        segments.push((current, args));
+    }
+}
+
+fn scan_number(bytes: &[u8], mut pos: usize) -> usize {
+    let digits = |mut pos: usize| {
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        pos
+    };
+    if matches!(bytes.get(pos), Some(b'+') | Some(b'-')) {
+        pos += 1;
+    }
+    pos = digits(pos);
+    if bytes.get(pos) == Some(&b'.') {
+        pos = digits(pos + 1);
+    }
+    if matches!(bytes.get(pos), Some(b'e') | Some(b'E')) {
+        let mut exp = pos + 1;
+        if matches!(bytes.get(exp), Some(b'+') | Some(b'-')) {
+            exp += 1;
+        }
+        if bytes.get(exp).is_some_and(u8::is_ascii_digit) {
+            pos = digits(exp);
+        }
+    }
+    pos
+}
+
+/// convert the segments to absolute `M L C Q A Z` commands, and transform
+/// them. Arcs can only be rotated, moved and uniformly scaled.
+fn transform_path(segments: &[(u8, Vec<f32>)], m: &Matrix) -> Option<Vec<(u8, Vec<f32>)>> {
+    let apply = |x: f32, y: f32| [m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]];
+    let mut out = Vec::with_capacity(segments.len());
+    let (mut x, mut y) = (0.0, 0.0);
+    let (mut start_x, mut start_y) = (0.0, 0.0);
+    // the last control point, for the smooth curves
+    let mut control: Option<(u8, f32, f32)> = None;
+
+    for (cmd, args) in segments {
+        let relative = cmd.is_ascii_lowercase();
+        let (dx, dy) = if relative { (x, y) } else { (0.0, 0.0) };
+        let abs = |i: usize| (args[i] + dx, args[i + 1] + dy);
+        let reflect = |kind: u8| match control {
+            Some((k, cx, cy)) if k == kind => (2.0 * x - cx, 2.0 * y - cy),
+            _ => (x, y),
+        };
+        let upper = cmd.to_ascii_uppercase();
+        let mut next_control = None;
+        let (cmd, points): (u8, Vec<(f32, f32)>) = match upper {
+            b'M' => {
+                let p = abs(0);
+                (start_x, start_y) = p;
+                (b'M', vec![p])
+            }
+            b'L' => (b'L', vec![abs(0)]),
+            b'H' => (b'L', vec![(args[0] + dx, y)]),
+            b'V' => (b'L', vec![(x, args[0] + if relative { y } else { 0.0 })]),
+            b'C' => {
+                let c2 = abs(2);
+                next_control = Some((b'C', c2.0, c2.1));
+                (b'C', vec![abs(0), c2, abs(4)])
+            }
+            b'S' => {
+                let c2 = abs(0);
+                next_control = Some((b'C', c2.0, c2.1));
+                (b'C', vec![reflect(b'C'), c2, abs(2)])
+            }
+            b'Q' => {
+                let c = abs(0);
+                next_control = Some((b'Q', c.0, c.1));
+                (b'Q', vec![c, abs(2)])
+            }
+            b'T' => {
+                let c = reflect(b'Q');
+                next_control = Some((b'Q', c.0, c.1));
+                (b'Q', vec![c, abs(0)])
+            }
+            b'A' => {
+                // only similarity transforms keep elliptical arcs, a
+                // reflection mirrors the rotation and the sweep
+                let scale = (m[0] * m[0] + m[1] * m[1]).sqrt();
+                let angle = m[1].atan2(m[0]).to_degrees();
+                let (rotation, sweep) = if (m[0] - m[3]).abs() < 1e-3 && (m[1] + m[2]).abs() < 1e-3
+                {
+                    (angle + args[2], args[4])
+                } else if (m[0] + m[3]).abs() < 1e-3 && (m[1] - m[2]).abs() < 1e-3 {
+                    (angle - args[2], 1.0 - args[4])
+                } else {
+                    return None;
+                };
+                let (ex, ey) = abs(5);
+                let [tx, ty] = apply(ex, ey);
+                out.push((
+                    b'A',
+                    vec![
+                        args[0] * scale,
+                        args[1] * scale,
+                        rotation,
+                        args[3],
+                        sweep,
+                        tx,
+                        ty,
+                    ],
+                ));
+                (x, y) = (ex, ey);
+                control = None;
+                continue;
+            }
+            _ => {
+                out.push((b'Z', Vec::new()));
+                (x, y) = (start_x, start_y);
+                control = None;
+                continue;
+            }
+        };
+        (x, y) = *points.last()?;
+        control = next_control;
+        out.push((
+            cmd,
+            points
+                .into_iter()
+                .flat_map(|(px, py)| apply(px, py))
+                .collect(),
+        ));
+    }
+    Some(out)
+}
+
+/// parse a transform list into a single matrix
+fn parse_transform(s: &str) -> Option<Matrix> {
+    let mut m = IDENTITY;
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let (name, tail) = rest.split_once('(')?;
+        let (args, tail) = tail.split_once(')')?;
+        let v = args
+            .split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let rad = |deg: f32| deg.to_radians();
+        let t = match (name.trim(), v.as_slice()) {
+            ("matrix", [a, b, c, d, e, f]) => [*a, *b, *c, *d, *e, *f],
+            ("translate", [x]) => [1.0, 0.0, 0.0, 1.0, *x, 0.0],
+            ("translate", [x, y]) => [1.0, 0.0, 0.0, 1.0, *x, *y],
+            ("scale", [s]) => [*s, 0.0, 0.0, *s, 0.0, 0.0],
+            ("scale", [sx, sy]) => [*sx, 0.0, 0.0, *sy, 0.0, 0.0],
+            ("rotate", [a]) => {
+                let (sin, cos) = rad(*a).sin_cos();
+                [cos, sin, -sin, cos, 0.0, 0.0]
+            }
+            ("rotate", [a, cx, cy]) => {
+                let (sin, cos) = rad(*a).sin_cos();
+                let rotate = [cos, sin, -sin, cos, 0.0, 0.0];
+                let to = [1.0, 0.0, 0.0, 1.0, *cx, *cy];
+                let back = [1.0, 0.0, 0.0, 1.0, -cx, -cy];
+                multiply(&multiply(&to, &rotate), &back)
+            }
+            ("skewX", [a]) => [1.0, 0.0, rad(*a).tan(), 1.0, 0.0, 0.0],
+            ("skewY", [a]) => [1.0, rad(*a).tan(), 0.0, 1.0, 0.0, 0.0],
+            _ => return None,
+        };
+        m = multiply(&m, &t);
+        rest = tail.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+    }
+    Some(m)
+}
+
+fn multiply(m: &Matrix, n: &Matrix) -> Matrix {
+    [
+        m[0] * n[0] + m[2] * n[1],
+        m[1] * n[0] + m[3] * n[1],
+        m[0] * n[2] + m[2] * n[3],
+        m[1] * n[2] + m[3] * n[3],
+        m[0] * n[4] + m[2] * n[5] + m[4],
+        m[1] * n[4] + m[3] * n[5] + m[5],
+    ]
+}
+
+/// how much the transform scales the stroke widths
+fn scale_of(m: &Matrix) -> f32 {
+    (m[0] * m[3] - m[1] * m[2]).abs().sqrt()
+}
+
+fn dedup_push(list: &mut Vec<String>, item: Option<String>) {
+    if let Some(item) = item {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+impl Color {
+    const BLACK: Color = Color {
+        rgb: [0, 0, 0],
+        alpha: 1.0,
+    };
+
+    fn parse(s: &str, current: Option<Color>) -> Option<Self> {
+        let rgb = match s {
+            "currentColor" => return current,
+            "black" => [0, 0, 0],
+            "white" => [255, 255, 255],
+            _ => {
+                let hex = s.strip_prefix('#')?;
+                let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+                match hex.len() {
+                    3 => [digit(0)? * 17, digit(1)? * 17, digit(2)? * 17],
+                    6 => [
+                        digit(0)? * 16 + digit(1)?,
+                        digit(2)? * 16 + digit(3)?,
+                        digit(4)? * 16 + digit(5)?,
+                    ],
+                    _ => return None,
+                }
+            }
+        };
+        Some(Self { rgb, alpha: 1.0 })
+    }
+}
+
+/// `#RRGGBB`, or `#AARRGGBB` if translucent
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [r, g, b] = self.rgb;
+        if self.alpha < 1.0 {
+            let a = (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+            write!(f, "#{a:02X}{r:02X}{g:02X}{b:02X}")
+        } else {
+            write!(f, "#{r:02X}{g:02X}{b:02X}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg viewbox="0 0 24 24" fill=none stroke=currentColor stroke-width=2 stroke-linecap=round xmlns=http://www.w3.org/2000/svg><circle cx=12 cy=12 r=10 /><path d="M8 12h8" style=stroke:#f00;opacity:.5 /><rect x=2 y=2 width=4 height=4 rx=1 fill=#000 stroke=none transform="rotate(90 4 4)"/><mask id=m /></svg>"#;
+
+    #[test]
+    fn path_data_should_be_normalized() {
+        assert_eq!(
+            path_data("M1.5.5l2-2a1 1 0 011 1 2 2 .5 003 3zm1 1 2 2", &IDENTITY).unwrap(),
+            "M1.5 0.5l2 -2a1 1 0 0 1 1 1a2 2 0.5 0 0 3 3zm1 1l2 2"
+        );
+        assert_eq!(
+            path_data("M1 1h2v2s1 1 2 2", &[1.0, 0.0, 0.0, 1.0, 10.0, 0.0]).unwrap(),
+            "M11 1L13 1L13 3C13 3 14 4 15 5"
+        );
+        assert!(path_data("M1 1a1 1 0 2 1 3 3", &IDENTITY).is_none());
+    }
+
+    #[test]
+    fn vector_drawable_should_work() {
+        let doc = IconDocument::parse(SVG).unwrap();
+        let converted = doc.to_vector_drawable("#000000");
+        assert_eq!(converted.unsupported, ["mask"]);
+        assert_eq!(
+            converted.output,
+            r##"<vector xmlns:android="http://schemas.android.com/apk/res/android"
+    android:width="24dp"
+    android:height="24dp"
+    android:viewportWidth="24"
+    android:viewportHeight="24">
+    <path
+        android:strokeColor="#000000"
+        android:strokeWidth="2"
+        android:strokeLineCap="round"
+        android:pathData="M2 12A10 10 0 1 0 22 12A10 10 0 1 0 2 12Z"/>
+    <path
+        android:strokeColor="#80FF0000"
+        android:strokeWidth="2"
+        android:strokeLineCap="round"
+        android:pathData="M8 12h8"/>
+    <path
+        android:fillColor="#000000"
+        android:pathData="M6 3L6 5A1 1 90 0 1 5 6L3 6A1 1 90 0 1 2 5L2 3A1 1 90 0 1 3 2L5 2A1 1 90 0 1 6 3Z"/>
+</vector>
+"##
+        );
+    }
+
+    #[test]
+    fn xaml_should_work() {
+        let doc = IconDocument::parse(SVG).unwrap();
+        let converted = doc.to_xaml_drawing_image("#0366d6");
+        assert!(converted.output.contains(r##"<DrawingGroup ClipGeometry="M0,0 H24 V24 H0 Z">
+      <GeometryDrawing Geometry="F1 M2 12A10 10 0 1 0 22 12A10 10 0 1 0 2 12Z">
+        <GeometryDrawing.Pen>
+          <Pen Brush="#0366D6" Thickness="2" StartLineCap="Round" EndLineCap="Round" LineJoin="Miter" MiterLimit="4" />"##));
+
+        let converted = doc.to_xaml_path_geometry();
+        assert_eq!(
+            converted.unsupported,
+            ["mask", "circle: stroke", "path: stroke"]
+        );
+        assert!(converted
+            .output
+            .ends_with(r#"FillRule="Nonzero" Figures="M6 3L6 5A1 1 90 0 1 5 6L3 6A1 1 90 0 1 2 5L2 3A1 1 90 0 1 3 2L5 2A1 1 90 0 1 6 3Z" />
+"#));
+    }
+}
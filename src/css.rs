@@ -0,0 +1,204 @@
+use crate::{Icon, IconError};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// how the class of an icon is named, after the prefix
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClassNaming {
+    /// `icon-heroicons-outline-trash`
+    #[default]
+    Full,
+    /// `icon-heroicons-trash`
+    SetAndName,
+    /// `icon-trash`
+    Name,
+}
+
+/// generate a stylesheet with a class per icon, e.g.
+/// `<span class="icon-heroicons-outline-trash"></span>`. The icon is used as
+/// a `mask-image` over `background-color: currentColor`, so it inherits the
+/// text color.
+#[derive(Debug, Clone)]
+pub struct CssBuilder {
+    prefix: String,
+    naming: ClassNaming,
+    size: String,
+    // class name -> data uri
+    icons: BTreeMap<String, String>,
+}
+
+impl ClassNaming {
+    pub(crate) fn class_name(&self, prefix: &str, icon: &Icon) -> String {
+        match self {
+            ClassNaming::Full => {
+                format!("{prefix}{}-{}-{}", icon.set(), icon.variant(), icon.name())
+            }
+            ClassNaming::SetAndName => format!("{prefix}{}-{}", icon.set(), icon.name()),
+            ClassNaming::Name => format!("{prefix}{}", icon.name()),
+        }
+    }
+}
+
+impl Default for CssBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CssBuilder {
+    pub fn new() -> Self {
+        Self {
+            prefix: "icon-".into(),
+            naming: ClassNaming::default(),
+            size: "1em".into(),
+            icons: BTreeMap::new(),
+        }
+    }
+
+    /// the prefix of the class names, `icon-` by default
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn naming(mut self, naming: ClassNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// the default width and height of the icons, `1em` by default
+    pub fn size(mut self, size: impl Into<String>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// the class of the icon as written in HTML. It is escaped in the
+    /// stylesheet, so names like `500px` are fine.
+    pub fn class_name(&self, icon: &Icon) -> String {
+        self.naming.class_name(&self.prefix, icon)
+    }
+
+    /// add an icon, rendered with its attributes. An icon with the same class
+    /// name replaces the previous one.
+    pub fn add(&mut self, icon: &Icon) -> Result<&mut Self, IconError> {
+        let uri = icon.to_data_uri()?;
+        self.icons.insert(self.class_name(icon), uri);
+        Ok(self)
+    }
+
+    pub fn extend<'a>(
+        &mut self,
+        icons: impl IntoIterator<Item = Icon<'a>>,
+    ) -> Result<&mut Self, IconError> {
+        for icon in icons {
+            self.add(&icon)?;
+        }
+        Ok(self)
+    }
+
+    pub fn build(&self) -> String {
+        if self.icons.is_empty() {
+            return String::new();
+        }
+
+        let selectors = self
+            .icons
+            .keys()
+            .map(|class| format!(".{}", escape_ident(class)))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let mut css = format!(
+            "{selectors} {{\n  display: inline-block;\n  width: {size};\n  height: {size};\n  background-color: currentColor;\n  -webkit-mask: no-repeat center / 100% 100%;\n  mask: no-repeat center / 100% 100%;\n}}\n",
+            size = self.size
+        );
+        for (class, uri) in &self.icons {
+            let class = escape_ident(class);
+            css.push_str(&format!(
+                "\n.{class} {{\n  -webkit-mask-image: url(\"{uri}\");\n  mask-image: url(\"{uri}\");\n}}\n"
+            ));
+        }
+        css
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.build())
+    }
+}
+
+/// escape a class name for a selector, like `CSS.escape` in browsers
+pub(crate) fn escape_ident(ident: &str) -> String {
+    let mut out = String::with_capacity(ident.len());
+    let first = ident.chars().next();
+    for (i, c) in ident.chars().enumerate() {
+        let leading_digit = c.is_ascii_digit() && (i == 0 || (i == 1 && first == Some('-')));
+        match c {
+            '\0' => out.push('\u{fffd}'),
+            c if c.is_ascii_control() || leading_digit => {
+                out.push_str(&format!("\\{:x} ", c as u32))
+            }
+            '-' if ident.len() == 1 => out.push_str("\\-"),
+            c if !c.is_ascii() || c.is_ascii_alphanumeric() || matches!(c, '-' | '_') => {
+                out.push(c)
+            }
+            c => {
+                out.push('\\');
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "heroicons", feature = "feather"))]
+    #[test]
+    fn css_builder_should_work() {
+        let mut builder = CssBuilder::new().size("1.25em");
+        builder
+            .add(&"heroicons:outline:trash".parse().unwrap())
+            .unwrap()
+            .add(&"feather:activity".parse().unwrap())
+            .unwrap();
+        let css = builder.build();
+        assert!(css.starts_with(
+            ".icon-feather-normal-activity,\n.icon-heroicons-outline-trash {\n  display: inline-block;\n  width: 1.25em;"
+        ));
+        assert!(css.contains(
+            "\n.icon-heroicons-outline-trash {\n  -webkit-mask-image: url(\"data:image/svg+xml,%3Csvg "
+        ));
+    }
+
+    #[cfg(feature = "feather")]
+    #[test]
+    fn css_builder_should_replace_root_attributes() {
+        let icon: Icon = "feather:activity".parse().unwrap();
+        let mut builder = CssBuilder::new();
+        builder.add(&icon.stroke_width("3")).unwrap();
+        let css = builder.build();
+        assert_eq!(css.matches(" stroke-width='3'").count(), 2);
+        assert!(!css.contains("stroke-width='2'"));
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn class_naming_should_be_configurable() {
+        let icon: Icon = "heroicons:solid:trash".parse().unwrap();
+        let builder = CssBuilder::new()
+            .prefix("i-")
+            .naming(ClassNaming::SetAndName);
+        assert_eq!(builder.class_name(&icon), "i-heroicons-trash");
+        let builder = builder.naming(ClassNaming::Name);
+        assert_eq!(builder.class_name(&icon), "i-trash");
+    }
+
+    #[test]
+    fn class_names_should_be_escaped() {
+        assert_eq!(escape_ident("icon-trash"), "icon-trash");
+        assert_eq!(escape_ident("500px"), "\\35 00px");
+        assert_eq!(escape_ident("-1"), "-\\31 ");
+        assert_eq!(escape_ident("-"), "\\-");
+        assert_eq!(escape_ident("i-a.b:c/d"), "i-a\\.b\\:c\\/d");
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Icon, IconError};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Icon<'_> {
+    /// the rendered icon as a compact, percent-encoded `data:` URI, to be used
+    /// in `<img src>` or in CSS as `url("...")`. It keeps spaces, single quotes
+    /// and parentheses, so the CSS `url()` must be double quoted.
+    pub fn to_data_uri(&self) -> Result<String, IconError> {
+        Ok(svg_data_uri(&self.to_xml()?))
+    }
+
+    /// the rendered icon as a base64 `data:` URI
+    pub fn to_base64_data_uri(&self) -> Result<String, IconError> {
+        Ok(svg_base64_data_uri(&self.to_xml()?))
+    }
+}
+
+/// encode an SVG document as a `data:` URI with minimal percent-encoding: double
+/// quotes are swapped for single quotes, whitespace is collapsed, and only the
+/// characters which are unsafe in a URI or in a double quoted CSS `url()` are
+/// encoded. An unquoted `url()` would end at the first space or parenthesis.
+pub fn svg_data_uri(svg: &str) -> String {
+    let mut out = String::with_capacity(svg.len() + svg.len() / 8 + 19);
+    out.push_str("data:image/svg+xml,");
+    let mut space = false;
+    for c in svg.trim().chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            out.push(' ');
+            space = false;
+        }
+        match c {
+            '"' => out.push('\''),
+            // values are single quoted once the double quotes are swapped. A
+            // numeric reference would bring a raw `#`, read as a fragment
+            '\'' => out.push_str("&apos;"),
+            '%' | '#' | '<' | '>' | '{' | '}' | '|' | '\\' | '^' | '`' | '[' | ']' => {
+                push_encoded(&mut out, c)
+            }
+            c if c.is_ascii_control() || !c.is_ascii() => push_encoded(&mut out, c),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// encode an SVG document as a base64 `data:` URI
+pub fn svg_base64_data_uri(svg: &str) -> String {
+    let bytes = svg.as_bytes();
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4 + 26);
+    out.push_str("data:image/svg+xml;base64,");
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn push_encoded(out: &mut String, c: char) {
+    let mut buf = [0; 4];
+    for b in c.encode_utf8(&mut buf).bytes() {
+        out.push('%');
+        out.push(
+            char::from_digit((b >> 4) as u32, 16)
+                .unwrap()
+                .to_ascii_uppercase(),
+        );
+        out.push(
+            char::from_digit((b & 0xf) as u32, 16)
+                .unwrap()
+                .to_ascii_uppercase(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_data_uri_should_encode_minimally() {
+        let svg = "<svg viewBox=\"0 0 24 24\"  xmlns=\"http://www.w3.org/2000/svg\">\n  <path d=\"M0 0\" fill=\"#000\" title=\"it's\"/>\n</svg>";
+        assert_eq!(
+            svg_data_uri(svg),
+            "data:image/svg+xml,%3Csvg viewBox='0 0 24 24' xmlns='http://www.w3.org/2000/svg'%3E %3Cpath d='M0 0' fill='%23000' title='it&apos;s'/%3E %3C/svg%3E"
+        );
+    }
+
+    #[test]
+    fn svg_data_uri_should_not_have_fragments() {
+        let uri = svg_data_uri("<svg><title>it's #1</title></svg>");
+        assert!(!uri.contains('#'));
+        assert!(uri.contains("it&apos;s %231"));
+    }
+
+    #[test]
+    fn svg_data_uri_should_be_safe_in_quoted_css_urls() {
+        let uri = svg_data_uri("<svg>\n  <g transform=\"rotate(45)\" data-x='\\\"'/>\r\n</svg>");
+        assert!(!uri.contains(['"', '\\', '\n', '\r']));
+        // but not in unquoted ones
+        assert!(uri.contains(" transform='rotate(45)'"));
+    }
+
+    #[test]
+    fn svg_base64_data_uri_should_work() {
+        assert_eq!(
+            svg_base64_data_uri("<svg/>"),
+            "data:image/svg+xml;base64,PHN2Zy8+"
+        );
+        assert_eq!(
+            svg_base64_data_uri("<svg />"),
+            "data:image/svg+xml;base64,PHN2ZyAvPg=="
+        );
+        assert_eq!(
+            svg_base64_data_uri("<svg  />"),
+            "data:image/svg+xml;base64,PHN2ZyAgLz4="
+        );
+    }
+}
@@ -0,0 +1,587 @@
+use crate::{ids::rewrite_attr, IconError};
+use std::{collections::HashSet, fmt};
+
+/// a parsed SVG icon: the root `<svg>` element and its children
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IconDocument {
+    // the xml declaration, doctype or comments in front of the root element
+    prolog: String,
+    root: Element,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// an SVG element. Attribute values and text are kept as written in the
+/// source, entities are not decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+    self_closing: bool,
+}
+
+/// the `viewBox` of an icon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewBox {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl IconDocument {
+    pub fn new(root: Element) -> Self {
+        Self {
+            prolog: String::new(),
+            root,
+        }
+    }
+
+    pub fn parse(svg: &str) -> Result<Self, IconError> {
+        let mut parser = Parser { src: svg, pos: 0 };
+        parser.skip_prolog();
+        let prolog = svg[..parser.pos].to_owned();
+        let root = parser.element()?;
+        parser.skip_prolog();
+        if parser.pos != svg.len() {
+            return Err(parser.error("unexpected content after the root element"));
+        }
+        if root.name != "svg" {
+            return Err(IconError::Parse {
+                pos: 0,
+                reason: "root element is not <svg>",
+            });
+        }
+        Ok(Self { prolog, root })
+    }
+
+    pub fn root(&self) -> &Element {
+        &self.root
+    }
+
+    pub fn root_mut(&mut self) -> &mut Element {
+        &mut self.root
+    }
+
+    pub fn into_root(self) -> Element {
+        self.root
+    }
+
+    /// the `viewBox` of the icon, falling back to its `width` and `height`
+    pub fn view_box(&self) -> Option<ViewBox> {
+        if let Some(view_box) = self.root.attr("viewbox").or(self.root.attr("viewBox")) {
+            return view_box.parse().ok();
+        }
+        let width = self.root.attr("width")?.parse().ok()?;
+        let height = self.root.attr("height")?.parse().ok()?;
+        Some(ViewBox {
+            min_x: 0.0,
+            min_y: 0.0,
+            width,
+            height,
+        })
+    }
+
+    /// prefix every `id` with `{prefix}-` and update all the references to it,
+    /// like `rewrite_ids`
+    pub fn rewrite_ids(&mut self, prefix: &str) {
+        let mut ids = HashSet::new();
+        self.root.visit(&mut |el| {
+            if let Some(id) = el.attr("id").filter(|id| !id.is_empty()) {
+                ids.insert(id.to_owned());
+            }
+        });
+        if ids.is_empty() {
+            return;
+        }
+
+        let ids: HashSet<&str> = ids.iter().map(String::as_str).collect();
+        self.root.visit_mut(&mut |el| {
+            for (name, value) in el.attrs.iter_mut() {
+                if let Some(rewritten) = rewrite_attr(name, value, &ids, prefix) {
+                    *value = rewritten;
+                }
+            }
+        });
+    }
+
+    /// serialize the icon as a standalone XML document: every attribute is
+    /// quoted, and the SVG attribute names are restored to their camel case
+    pub fn to_xml(&self) -> String {
+        let mut out = String::new();
+        self.root.write_xml(&mut out, true);
+        out
+    }
+}
+
+impl std::str::FromStr for IconDocument {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// serialize the icon in the minified form used by the bundled icons
+impl fmt::Display for IconDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.prolog)?;
+        self.root.fmt(f)
+    }
+}
+
+impl Element {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            attrs: Vec::new(),
+            children: Vec::new(),
+            self_closing: true,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn attrs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// set an attribute, replacing the existing value if there is one
+    pub fn set_attr(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        let name = name.into();
+        let value = value.into();
+        match self.attrs.iter_mut().find(|(k, _)| *k == name) {
+            Some((_, v)) => *v = value,
+            None => self.attrs.push((name, value)),
+        }
+        self
+    }
+
+    pub fn remove_attr(&mut self, name: &str) -> Option<String> {
+        let pos = self.attrs.iter().position(|(k, _)| k == name)?;
+        Some(self.attrs.remove(pos).1)
+    }
+
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.children
+    }
+
+    pub fn push(&mut self, child: impl Into<Node>) -> &mut Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// the child elements, skipping text
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(el) => Some(el),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut Element> {
+        self.children.iter_mut().filter_map(|node| match node {
+            Node::Element(el) => Some(el),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// call `f` on this element and all its descendants, in document order
+    pub fn visit<'s>(&'s self, f: &mut impl FnMut(&'s Element)) {
+        f(self);
+        for el in self.elements() {
+            el.visit(f);
+        }
+    }
+
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut Element)) {
+        f(self);
+        for el in self.elements_mut() {
+            el.visit_mut(f);
+        }
+    }
+
+    fn write_xml(&self, out: &mut String, root: bool) {
+        let name = xml_name(&self.name);
+        out.push('<');
+        out.push_str(name);
+        for (k, v) in &self.attrs {
+            out.push(' ');
+            out.push_str(xml_name(k));
+            out.push_str("=\"");
+            out.push_str(&v.replace('"', "&quot;"));
+            out.push('"');
+        }
+        if root && self.attr("xmlns").is_none() {
+            out.push_str(" xmlns=\"http://www.w3.org/2000/svg\"");
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for child in &self.children {
+            match child {
+                Node::Element(el) => el.write_xml(out, false),
+                Node::Text(text) => out.push_str(text),
+            }
+        }
+        out.push_str("</");
+        out.push_str(name);
+        out.push('>');
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.name)?;
+        let mut unquoted = false;
+        for (k, v) in &self.attrs {
+            f.write_str(" ")?;
+            f.write_str(k)?;
+            unquoted = false;
+            if v.is_empty() {
+                continue;
+            }
+            if v.bytes().all(is_unquoted) {
+                write!(f, "={v}")?;
+                unquoted = true;
+            } else if v.contains('"') && !v.contains('\'') {
+                write!(f, "='{v}'")?;
+            } else {
+                write!(f, "=\"{}\"", v.replace('"', "&#34;"))?;
+            }
+        }
+
+        if self.children.is_empty() && self.self_closing {
+            return f.write_str(if unquoted { " />" } else { "/>" });
+        }
+        f.write_str(">")?;
+        for child in &self.children {
+            match child {
+                Node::Element(el) => el.fmt(f)?,
+                Node::Text(text) => f.write_str(text)?,
+            }
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+impl From<Element> for Node {
+    fn from(el: Element) -> Self {
+        Node::Element(el)
+    }
+}
+
+impl From<String> for Node {
+    fn from(text: String) -> Self {
+        Node::Text(text)
+    }
+}
+
+impl std::str::FromStr for ViewBox {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = IconError::Parse {
+            pos: 0,
+            reason: "invalid viewBox",
+        };
+        let mut values = s
+            .split(|c: char| c.is_ascii_whitespace() || c == ',')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f32>().map_err(|_| err.clone()));
+        let mut next = || values.next().unwrap_or(Err(err.clone()));
+        Ok(Self {
+            min_x: next()?,
+            min_y: next()?,
+            width: next()?,
+            height: next()?,
+        })
+    }
+}
+
+impl fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.min_x, self.min_y, self.width, self.height
+        )
+    }
+}
+
+#[inline]
+fn is_unquoted(b: u8) -> bool {
+    !b.is_ascii_whitespace() && !matches!(b, b'"' | b'\'' | b'=' | b'<' | b'>' | b'`')
+}
+
+/// SVG is case sensitive, but the minifier lowercases the names
+pub(crate) fn xml_name(name: &str) -> &str {
+    const NAMES: &[&str] = &[
+        "viewBox",
+        "preserveAspectRatio",
+        "gradientUnits",
+        "gradientTransform",
+        "patternUnits",
+        "patternContentUnits",
+        "patternTransform",
+        "clipPathUnits",
+        "maskUnits",
+        "maskContentUnits",
+        "spreadMethod",
+        "pathLength",
+        "markerWidth",
+        "markerHeight",
+        "markerUnits",
+        "refX",
+        "refY",
+        "stdDeviation",
+        "attributeName",
+        "repeatCount",
+        "keyTimes",
+        "keySplines",
+        "calcMode",
+        "clipPath",
+        "linearGradient",
+        "radialGradient",
+        "foreignObject",
+        "textPath",
+        "animateTransform",
+        "animateMotion",
+        "feGaussianBlur",
+    ];
+    NAMES
+        .iter()
+        .find(|n| n.eq_ignore_ascii_case(name))
+        .copied()
+        .unwrap_or(name)
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &'static str) -> IconError {
+        IconError::Parse {
+            pos: self.pos,
+            reason,
+        }
+    }
+
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// skip whitespace, the xml declaration, doctype and comments
+    fn skip_prolog(&mut self) {
+        loop {
+            self.skip_whitespace();
+            let end = if self.rest().starts_with("<?") {
+                "?>"
+            } else if self.rest().starts_with("<!--") {
+                "-->"
+            } else if self.rest().starts_with("<!") {
+                ">"
+            } else {
+                return;
+            };
+            match self.rest().find(end) {
+                Some(n) => self.pos += n + end.len(),
+                None => self.pos = self.src.len(),
+            }
+        }
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn name(&mut self) -> Result<String, IconError> {
+        let len = self
+            .rest()
+            .find(|c: char| c.is_ascii_whitespace() || matches!(c, '=' | '>' | '/'))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expect a name"));
+        }
+        let name = self.rest()[..len].to_owned();
+        self.pos += len;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, IconError> {
+        if !self.eat("<") {
+            return Err(self.error("expect an element"));
+        }
+        let mut el = Element::new(self.name()?);
+
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(el);
+            }
+            if self.eat(">") {
+                break;
+            }
+            if self.rest().is_empty() {
+                return Err(self.error("unclosed start tag"));
+            }
+
+            let name = self.name()?;
+            let value = if self.eat("=") {
+                self.attr_value()?
+            } else {
+                String::new()
+            };
+            el.attrs.push((name, value));
+        }
+
+        el.self_closing = false;
+        loop {
+            if self.eat("</") {
+                let name = self.name()?;
+                if name != el.name {
+                    return Err(self.error("mismatched end tag"));
+                }
+                self.skip_whitespace();
+                if !self.eat(">") {
+                    return Err(self.error("unclosed end tag"));
+                }
+                return Ok(el);
+            }
+            if self.rest().starts_with("<!--") {
+                self.pos += self.rest().find("-->").map_or(self.rest().len(), |n| n + 3);
+            } else if self.rest().starts_with('<') {
+                let child = self.element()?;
+                el.children.push(Node::Element(child));
+            } else if self.rest().is_empty() {
+                return Err(self.error("unclosed element"));
+            } else {
+                let len = self.rest().find('<').unwrap_or(self.rest().len());
+                el.children.push(Node::Text(self.rest()[..len].to_owned()));
+                self.pos += len;
+            }
+        }
+    }
+
+    fn attr_value(&mut self) -> Result<String, IconError> {
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let len = rest[1..]
+                    .find(quote)
+                    .ok_or_else(|| self.error("unclosed attribute value"))?;
+                let value = rest[1..1 + len].to_owned();
+                self.pos += len + 2;
+                Ok(value)
+            }
+            _ => {
+                let len = rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                let value = rest[..len].to_owned();
+                self.pos += len;
+                Ok(value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg class="bi bi-alarm" viewbox="0 0 16 16" fill=currentColor height=16 width=16 xmlns=http://www.w3.org/2000/svg><g id=a style=display:none></g><path d="M8.5 5.5z"/><circle cx=8 cy=8 r=2 /></svg>"#;
+
+    #[test]
+    fn document_should_round_trip() {
+        let doc = IconDocument::parse(SVG).unwrap();
+        assert_eq!(doc.to_string(), SVG);
+        assert_eq!(doc.root().elements().count(), 3);
+        assert_eq!(
+            doc.view_box(),
+            Some(ViewBox {
+                min_x: 0.0,
+                min_y: 0.0,
+                width: 16.0,
+                height: 16.0
+            })
+        );
+    }
+
+    #[test]
+    fn document_should_be_editable() {
+        let mut doc = IconDocument::parse(SVG).unwrap();
+        let root = doc.root_mut();
+        root.set_attr("class", "h-4 w-4").remove_attr("height");
+        root.elements_mut()
+            .nth(2)
+            .unwrap()
+            .set_attr("fill", "rgb(1, 2, 3)");
+        assert_eq!(
+            doc.to_string(),
+            r#"<svg class="h-4 w-4" viewbox="0 0 16 16" fill=currentColor width=16 xmlns=http://www.w3.org/2000/svg><g id=a style=display:none></g><path d="M8.5 5.5z"/><circle cx=8 cy=8 r=2 fill="rgb(1, 2, 3)"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn document_should_serialize_to_xml() {
+        let mut doc = IconDocument::parse(SVG).unwrap();
+        doc.rewrite_ids("x");
+        assert_eq!(
+            doc.to_xml(),
+            r#"<svg class="bi bi-alarm" viewBox="0 0 16 16" fill="currentColor" height="16" width="16" xmlns="http://www.w3.org/2000/svg"><g id="x-a" style="display:none"/><path d="M8.5 5.5z"/><circle cx="8" cy="8" r="2"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn rewrite_ids_should_prefix_each_reference_once() {
+        let mut doc = IconDocument::parse(
+            r##"<svg><g id=a /><g id=x-a /><path fill="url(#a)" stroke="url(#x-a)"/></svg>"##,
+        )
+        .unwrap();
+        doc.rewrite_ids("x");
+        assert_eq!(
+            doc.to_string(),
+            r##"<svg><g id=x-a /><g id=x-x-a /><path fill=url(#x-a) stroke=url(#x-x-a) /></svg>"##
+        );
+    }
+
+    #[test]
+    fn invalid_document_should_fail() {
+        assert!(IconDocument::parse("<svg><path></svg>").is_err());
+        assert!(IconDocument::parse("<div></div>").is_err());
+        assert!(IconDocument::parse("<svg viewbox=\"0 0").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IconError {
+    /// the SVG could not be parsed into an `IconDocument`
+    Parse { pos: usize, reason: &'static str },
+    /// the icon set or variant is unknown, or not enabled by the cargo features
+    UnknownIconType(String),
+    /// no icon with the canonical id (`set:variant:name`)
+    NotFound(String),
+    /// the icon could not be rendered into an image
+    Render(String),
+}
+
+impl fmt::Display for IconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IconError::Parse { pos, reason } => write!(f, "invalid svg at {pos}: {reason}"),
+            IconError::UnknownIconType(s) => write!(f, "unknown icon type: {s}"),
+            IconError::NotFound(id) => write!(f, "icon not found: {id}"),
+            IconError::Render(reason) => write!(f, "failed to render icon: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for IconError {}
@@ -0,0 +1,338 @@
+use crate::{compose::num, compose::DROPPED_ATTRS, Element, Icon, IconDocument, IconError};
+use std::{fs, io, path::Path};
+
+/// the sizes of the images in `favicon.ico`
+const ICO_SIZES: &[u32] = &[16, 32, 48];
+/// the sizes of the apple touch icons, for iPhone, iPad Pro, iPad and older
+/// iPhones
+const APPLE_TOUCH_SIZES: &[u32] = &[180, 167, 152, 120];
+/// the sizes of the icons in `site.webmanifest`
+const MANIFEST_SIZES: &[u32] = &[192, 512];
+/// the size of the generated SVG, in user units
+const SIDE: f32 = 100.0;
+
+/// generate the favicons and app icons of a site from an icon, offline
+#[derive(Debug, Clone)]
+pub struct FaviconBuilder<'a> {
+    icon: Icon<'a>,
+    foreground: String,
+    background: Option<String>,
+    dark: Option<(String, Option<String>)>,
+    padding: f32,
+    radius: f32,
+    path: String,
+}
+
+/// a generated file, e.g. `favicon.ico`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FaviconFile {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl<'a> FaviconBuilder<'a> {
+    /// a black icon on a transparent background, with a padding of 1/8
+    pub fn new(icon: Icon<'a>) -> Self {
+        Self {
+            icon,
+            foreground: "#000".into(),
+            background: None,
+            dark: None,
+            padding: 0.125,
+            radius: 0.0,
+            path: "/".into(),
+        }
+    }
+
+    pub fn foreground(mut self, color: impl Into<String>) -> Self {
+        self.foreground = color.into();
+        self
+    }
+
+    pub fn background(mut self, color: impl Into<String>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// the colors of the SVG favicon when the browser prefers a dark color
+    /// scheme, the background stays transparent if `None`
+    pub fn dark(mut self, foreground: impl Into<String>, background: Option<&str>) -> Self {
+        self.dark = Some((foreground.into(), background.map(Into::into)));
+        self
+    }
+
+    /// the space around the icon, as a fraction of the favicon size
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding.clamp(0.0, 0.45);
+        self
+    }
+
+    /// the corner radius of the background, as a fraction of the favicon size
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius.clamp(0.0, 0.5);
+        self
+    }
+
+    /// the url path the files are served from in `site.webmanifest`, `/` by
+    /// default
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        let mut path = path.into();
+        if !path.ends_with('/') {
+            path.push('/');
+        }
+        self.path = path;
+        self
+    }
+
+    /// the SVG favicon, with `prefers-color-scheme` styles if dark colors are
+    /// set
+    pub fn to_svg(&self) -> Result<String, IconError> {
+        let mut root = self.render(self.background.as_deref(), self.radius)?;
+        let Some((foreground, background)) = &self.dark else {
+            return Ok(IconDocument::new(root).to_xml());
+        };
+
+        let mut rule = format!(".fg{{color:{foreground}}}");
+        match (background, &self.background) {
+            (Some(background), _) => rule.push_str(&format!(".bg{{fill:{background}}}")),
+            (None, Some(_)) => rule.push_str(".bg{fill:none}"),
+            (None, None) => {}
+        }
+        // a background is needed to switch its color in dark mode
+        if background.is_some() && self.background.is_none() {
+            root.children_mut()
+                .insert(0, self.background_rect("none", self.radius).into());
+        }
+        let mut style = Element::new("style");
+        style.push(format!("@media (prefers-color-scheme:dark){{{rule}}}"));
+        root.children_mut().insert(0, style.into());
+        Ok(IconDocument::new(root).to_xml())
+    }
+
+    /// `favicon.ico`, with PNG images of 16, 32 and 48 pixels
+    pub fn to_ico(&self) -> Result<Vec<u8>, IconError> {
+        let svg = IconDocument::new(self.render(self.background.as_deref(), self.radius)?).to_xml();
+        let images = ICO_SIZES
+            .iter()
+            .map(|&size| Ok((size, crate::rasterize_svg(&svg, size, size)?.to_png()?)))
+            .collect::<Result<Vec<_>, IconError>>()?;
+        Ok(encode_ico(&images))
+    }
+
+    /// a PNG of the favicon
+    pub fn to_png(&self, size: u32) -> Result<Vec<u8>, IconError> {
+        let svg = IconDocument::new(self.render(self.background.as_deref(), self.radius)?).to_xml();
+        crate::rasterize_svg(&svg, size, size)?.to_png()
+    }
+
+    /// the `icons`, `theme_color` and `background_color` members of a
+    /// `site.webmanifest`
+    pub fn manifest(&self) -> String {
+        let icons = MANIFEST_SIZES
+            .iter()
+            .map(|size| {
+                format!(
+                    "    {{ \"src\": \"{}android-chrome-{size}x{size}.png\", \"sizes\": \"{size}x{size}\", \"type\": \"image/png\" }}",
+                    self.path
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let mut manifest = format!("{{\n  \"icons\": [\n{icons}\n  ]");
+        if let Some(background) = &self.background {
+            manifest.push_str(&format!(
+                ",\n  \"theme_color\": \"{background}\",\n  \"background_color\": \"{background}\""
+            ));
+        }
+        manifest.push_str("\n}\n");
+        manifest
+    }
+
+    /// all the files: `favicon.ico`, `favicon.svg`, the apple touch icons, the
+    /// manifest icons and `site.webmanifest`.
+    ///
+    /// iOS rounds the corners of apple touch icons and shows transparency as
+    /// black, so they are square, on a white background unless one is set.
+    pub fn build(&self) -> Result<Vec<FaviconFile>, IconError> {
+        let mut files = vec![
+            FaviconFile::new("favicon.ico", self.to_ico()?),
+            FaviconFile::new("favicon.svg", self.to_svg()?),
+        ];
+
+        let background = self.background.as_deref().unwrap_or("#fff");
+        let svg = IconDocument::new(self.render(Some(background), 0.0)?).to_xml();
+        for &size in APPLE_TOUCH_SIZES {
+            let name = match size {
+                180 => "apple-touch-icon.png".to_owned(),
+                _ => format!("apple-touch-icon-{size}x{size}.png"),
+            };
+            let png = crate::rasterize_svg(&svg, size, size)?.to_png()?;
+            files.push(FaviconFile::new(name, png));
+        }
+
+        for &size in MANIFEST_SIZES {
+            files.push(FaviconFile::new(
+                format!("android-chrome-{size}x{size}.png"),
+                self.to_png(size)?,
+            ));
+        }
+        files.push(FaviconFile::new("site.webmanifest", self.manifest()));
+        Ok(files)
+    }
+
+    /// write all the files into the directory, which is created if needed
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let files = self
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::create_dir_all(dir)?;
+        for file in files {
+            fs::write(dir.join(&file.name), &file.data)?;
+        }
+        Ok(())
+    }
+
+    /// the icon centered on the background, in a `0 0 100 100` viewBox
+    fn render(&self, background: Option<&str>, radius: f32) -> Result<Element, IconError> {
+        let doc = self.icon.to_document()?;
+        let view_box = doc.view_box().ok_or(IconError::Parse {
+            pos: 0,
+            reason: "icon has no viewBox",
+        })?;
+
+        let mut root = Element::new("svg");
+        root.set_attr("xmlns", "http://www.w3.org/2000/svg")
+            .set_attr("viewbox", format!("0 0 {SIDE} {SIDE}"));
+        if let Some(background) = background {
+            root.push(self.background_rect(background, radius));
+        }
+
+        let icon_root = doc.into_root();
+        let mut layer = Element::new("svg");
+        let offset = SIDE * self.padding;
+        let size = SIDE - 2.0 * offset;
+        layer
+            .set_attr("class", "fg")
+            .set_attr("x", num(offset))
+            .set_attr("y", num(offset))
+            .set_attr("width", num(size))
+            .set_attr("height", num(size))
+            .set_attr("viewbox", view_box.to_string())
+            .set_attr("color", self.foreground.as_str());
+        for (k, v) in icon_root.attrs() {
+            if !DROPPED_ATTRS.contains(&k) && !k.eq_ignore_ascii_case("viewbox") {
+                layer.set_attr(k, v);
+            }
+        }
+        if layer.attr("fill").is_none() {
+            layer.set_attr("fill", "currentColor");
+        }
+        for child in icon_root.children() {
+            layer.push(child.clone());
+        }
+        root.push(layer);
+        Ok(root)
+    }
+
+    fn background_rect(&self, fill: &str, radius: f32) -> Element {
+        let mut rect = Element::new("rect");
+        rect.set_attr("class", "bg")
+            .set_attr("width", num(SIDE))
+            .set_attr("height", num(SIDE))
+            .set_attr("fill", fill);
+        if radius > 0.0 {
+            rect.set_attr("rx", num(SIDE * radius));
+        }
+        rect
+    }
+}
+
+impl FaviconFile {
+    fn new(name: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            data: data.into(),
+        }
+    }
+}
+
+/// an ICO file with PNG images, supported since Windows Vista and by all
+/// browsers
+fn encode_ico(images: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut header = vec![0, 0, 1, 0];
+    header.extend((images.len() as u16).to_le_bytes());
+
+    let mut offset = 6 + 16 * images.len() as u32;
+    let mut data = Vec::new();
+    for (size, png) in images {
+        // 0 means 256 pixels
+        let side = if *size >= 256 { 0 } else { *size as u8 };
+        header.extend([side, side, 0, 0]);
+        header.extend(1u16.to_le_bytes());
+        header.extend(32u16.to_le_bytes());
+        header.extend((png.len() as u32).to_le_bytes());
+        header.extend(offset.to_le_bytes());
+        offset += png.len() as u32;
+        data.extend_from_slice(png);
+    }
+    header.extend(data);
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ico_should_contain_every_size() {
+        let pngs = vec![(16, vec![1, 2, 3]), (256, vec![4, 5])];
+        let ico = encode_ico(&pngs);
+        assert_eq!(&ico[..6], &[0, 0, 1, 0, 2, 0]);
+        // first entry: 16x16, 3 bytes at 38
+        assert_eq!(&ico[6..10], &[16, 16, 0, 0]);
+        assert_eq!(&ico[14..22], &[3, 0, 0, 0, 38, 0, 0, 0]);
+        // second entry: 256x256, 2 bytes at 41
+        assert_eq!(&ico[22..26], &[0, 0, 0, 0]);
+        assert_eq!(&ico[30..38], &[2, 0, 0, 0, 41, 0, 0, 0]);
+        assert_eq!(&ico[38..], &[1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn favicon_builder_should_work() {
+        let icon: Icon = "heroicons:solid:bolt".parse().unwrap();
+        let builder = FaviconBuilder::new(icon)
+            .foreground("#fff")
+            .background("#4f46e5")
+            .dark("#4f46e5", Some("#fff"))
+            .radius(0.25);
+        let files = builder.build().unwrap();
+        let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "favicon.ico",
+                "favicon.svg",
+                "apple-touch-icon.png",
+                "apple-touch-icon-167x167.png",
+                "apple-touch-icon-152x152.png",
+                "apple-touch-icon-120x120.png",
+                "android-chrome-192x192.png",
+                "android-chrome-512x512.png",
+                "site.webmanifest",
+            ]
+        );
+
+        let svg = String::from_utf8(files[1].data.clone()).unwrap();
+        assert!(svg.starts_with(r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100"><style>@media (prefers-color-scheme:dark){.fg{color:#4f46e5}.bg{fill:#fff}}</style><rect class="bg" width="100" height="100" fill="#4f46e5" rx="25"/><svg class="fg" x="12.5" y="12.5" width="75" height="75" viewBox="0 0 24 24" color="#fff""##));
+
+        let png = crate::RgbaImage::from_png(&files[2].data).unwrap();
+        assert_eq!((png.width, png.height), (180, 180));
+        // square corners, filled with the background
+        assert_eq!(&png.data[..4], &[0x4f, 0x46, 0xe5, 255]);
+        assert!(String::from_utf8(files[8].data.clone())
+            .unwrap()
+            .contains(r##""theme_color": "#4f46e5""##));
+    }
+}
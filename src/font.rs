@@ -0,0 +1,764 @@
+use crate::{css::escape_ident, ClassNaming, Icon, IconError};
+use kurbo::{BezPath, CubicBez, PathEl, Point, Shape};
+use resvg::{tiny_skia, usvg};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::Path,
+};
+use write_fonts::{
+    tables::{
+        cmap::Cmap,
+        glyf::{Bbox, GlyfLocaBuilder, Glyph, SimpleGlyph},
+        head::Head,
+        hhea::Hhea,
+        hmtx::{Hmtx, LongMetric},
+        maxp::Maxp,
+        name::{Name, NameRecord},
+        os2::Os2,
+        post::Post,
+    },
+    types::{FWord, Fixed, GlyphId, NameId, Tag, UfWord},
+    FontBuilder,
+};
+
+const UNITS_PER_EM: u16 = 1000;
+const ASCENT: i16 = 850;
+const DESCENT: i16 = -150;
+/// the Private Use Area of the Basic Multilingual Plane
+const PUA_START: u32 = 0xe000;
+const PUA_LEN: u32 = 0xf8ff - 0xe000 + 1;
+
+/// generate an icon font (TTF and WOFF2), with a stylesheet using `::before`
+/// content classes and a JSON map of the codepoints.
+///
+/// Codepoints are derived from a hash of the canonical icon ids. When hashes
+/// collide, an icon added later could take the codepoint of another one, so
+/// `write_to` keeps the codepoints of the map already in the directory, and
+/// the previous map can be passed to `codepoints` too.
+///
+/// Shapes are filled with the nonzero rule, with the `evenodd` ones converted
+/// to it, and strokes are converted to outlines. Other paint (colors, opacity)
+/// is lost.
+#[derive(Debug, Clone)]
+pub struct IconFontBuilder {
+    family: String,
+    prefix: String,
+    naming: ClassNaming,
+    font_url: Option<String>,
+    seed: BTreeMap<String, char>,
+    // canonical id -> glyph
+    glyphs: BTreeMap<String, IconGlyph>,
+}
+
+#[derive(Debug, Clone)]
+struct IconGlyph {
+    class: String,
+    outline: BezPath,
+}
+
+impl IconFontBuilder {
+    pub fn new(family: impl Into<String>) -> Self {
+        Self {
+            family: family.into(),
+            prefix: "icon-".into(),
+            naming: ClassNaming::default(),
+            font_url: None,
+            seed: BTreeMap::new(),
+            glyphs: BTreeMap::new(),
+        }
+    }
+
+    /// the prefix of the class names, `icon-` by default
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn naming(mut self, naming: ClassNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// the url of the font files in the stylesheet, without the extension.
+    /// The family name by default.
+    pub fn font_url(mut self, url: impl Into<String>) -> Self {
+        self.font_url = Some(url.into());
+        self
+    }
+
+    /// keep the codepoints of a previous build, as written by `to_json`
+    pub fn codepoints(mut self, json: &str) -> Result<Self, IconError> {
+        self.seed.extend(parse_codepoints(json)?);
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// add an icon, rendered with its attributes. An icon with the same id
+    /// replaces the previous one.
+    pub fn add(&mut self, icon: &Icon) -> Result<&mut Self, IconError> {
+        let glyph = IconGlyph {
+            class: self.naming.class_name(&self.prefix, icon),
+            outline: outline(icon)?,
+        };
+        self.glyphs.insert(icon.id(), glyph);
+        Ok(self)
+    }
+
+    pub fn extend<'a>(
+        &mut self,
+        icons: impl IntoIterator<Item = Icon<'a>>,
+    ) -> Result<&mut Self, IconError> {
+        for icon in icons {
+            self.add(&icon)?;
+        }
+        Ok(self)
+    }
+
+    /// the codepoint of every icon, by canonical id
+    pub fn to_codepoints(&self) -> BTreeMap<String, char> {
+        let mut used = BTreeSet::new();
+        let mut codepoints = BTreeMap::new();
+        for id in self.glyphs.keys() {
+            if let Some(ch) = self.seed.get(id) {
+                if used.insert(*ch) {
+                    codepoints.insert(id.clone(), *ch);
+                }
+            }
+        }
+        // seeded codepoints are reserved even if the icon is gone, so that
+        // they are never reused for another icon
+        used.extend(self.seed.values().copied());
+
+        for id in self.glyphs.keys() {
+            if codepoints.contains_key(id) {
+                continue;
+            }
+            let mut n = fnv1a(id.as_bytes()) % PUA_LEN;
+            let ch = loop {
+                let ch = char::from_u32(PUA_START + n).unwrap();
+                if used.insert(ch) {
+                    break ch;
+                }
+                n = (n + 1) % PUA_LEN;
+            };
+            codepoints.insert(id.clone(), ch);
+        }
+        codepoints
+    }
+
+    /// the codepoint map, e.g. `{"heroicons:outline:trash": 57345}`
+    pub fn to_json(&self) -> String {
+        let map = self
+            .to_codepoints()
+            .into_iter()
+            .map(|(id, ch)| (id, ch as u32))
+            .collect::<BTreeMap<_, _>>();
+        serde_json::to_string_pretty(&map).expect("a map of numbers is valid json") + "\n"
+    }
+
+    pub fn to_css(&self) -> String {
+        let url = self.font_url.as_deref().unwrap_or(&self.family);
+        let mut css = format!(
+            "@font-face {{\n  font-family: \"{family}\";\n  src: url(\"{url}.woff2\") format(\"woff2\"),\n    url(\"{url}.ttf\") format(\"truetype\");\n  font-display: block;\n}}\n\n[class^=\"{prefix}\"]::before,\n[class*=\" {prefix}\"]::before {{\n  font-family: \"{family}\" !important;\n  font-style: normal;\n  font-weight: normal;\n  font-variant: normal;\n  line-height: 1;\n  text-transform: none;\n  -webkit-font-smoothing: antialiased;\n  -moz-osx-font-smoothing: grayscale;\n}}\n",
+            family = self.family,
+            prefix = self.prefix,
+        );
+        let codepoints = self.to_codepoints();
+        for (id, glyph) in &self.glyphs {
+            css.push_str(&format!(
+                "\n.{}::before {{\n  content: \"\\{:x}\";\n}}\n",
+                escape_ident(&glyph.class),
+                codepoints[id] as u32
+            ));
+        }
+        css
+    }
+
+    pub fn to_ttf(&self) -> Result<Vec<u8>, IconError> {
+        let codepoints = self.to_codepoints();
+        let err = |e: &dyn std::fmt::Display| IconError::Render(e.to_string());
+
+        let mut glyf = GlyfLocaBuilder::new();
+        glyf.add_glyph(&Glyph::Empty).map_err(|e| err(&e))?;
+        let mut names = vec![".notdef".to_owned()];
+        let mut mappings = Vec::new();
+        let mut metrics = vec![LongMetric::new(UNITS_PER_EM, 0)];
+        let mut bbox: Option<Bbox> = None;
+        let (mut max_points, mut max_contours) = (0, 0);
+
+        for (i, (id, glyph)) in self.glyphs.iter().enumerate() {
+            let simple = if glyph.outline.elements().is_empty() {
+                Glyph::Empty
+            } else {
+                let simple = SimpleGlyph::from_bezpath(&glyph.outline)
+                    .map_err(|e| err(&format!("{e:?}")))?;
+                max_points = max_points.max(simple.contours.iter().map(|c| c.len()).sum());
+                max_contours = max_contours.max(simple.contours.len());
+                bbox = Some(bbox.map_or(simple.bbox, |b| b.union(simple.bbox)));
+                Glyph::Simple(simple)
+            };
+            let lsb = simple.bbox().map_or(0, |b| b.x_min);
+            metrics.push(LongMetric::new(UNITS_PER_EM, lsb));
+            glyf.add_glyph(&simple).map_err(|e| err(&e))?;
+            names.push(glyph.class.trim_start_matches(&self.prefix).to_owned());
+            mappings.push((codepoints[id], GlyphId::new(i as u32 + 1)));
+        }
+
+        let num_glyphs = names.len() as u16;
+        let bbox = bbox.unwrap_or_default();
+        let (glyf, loca, loca_format) = glyf.build();
+        let head = Head {
+            font_revision: Fixed::from_f64(1.0),
+            units_per_em: UNITS_PER_EM,
+            x_min: bbox.x_min,
+            y_min: bbox.y_min,
+            x_max: bbox.x_max,
+            y_max: bbox.y_max,
+            lowest_rec_ppem: 8,
+            index_to_loc_format: loca_format as i16,
+            ..Default::default()
+        };
+        let hhea = Hhea {
+            ascender: FWord::new(ASCENT),
+            descender: FWord::new(DESCENT),
+            advance_width_max: UfWord::new(UNITS_PER_EM),
+            min_left_side_bearing: FWord::new(bbox.x_min.min(0)),
+            min_right_side_bearing: FWord::new((UNITS_PER_EM as i16 - bbox.x_max).min(0)),
+            x_max_extent: FWord::new(bbox.x_max),
+            caret_slope_rise: 1,
+            number_of_h_metrics: num_glyphs,
+            ..Default::default()
+        };
+        let maxp = Maxp {
+            num_glyphs,
+            max_points: Some(max_points as u16),
+            max_contours: Some(max_contours as u16),
+            max_composite_points: Some(0),
+            max_composite_contours: Some(0),
+            max_zones: Some(1),
+            max_twilight_points: Some(0),
+            max_storage: Some(0),
+            max_function_defs: Some(0),
+            max_instruction_defs: Some(0),
+            max_stack_elements: Some(0),
+            max_size_of_instructions: Some(0),
+            max_component_elements: Some(0),
+            max_component_depth: Some(0),
+        };
+        let first = mappings.iter().map(|(ch, _)| *ch as u16).min().unwrap_or(0);
+        let last = mappings.iter().map(|(ch, _)| *ch as u16).max().unwrap_or(0);
+        let os2 = Os2 {
+            x_avg_char_width: UNITS_PER_EM as i16,
+            ach_vend_id: Tag::new(b"NONE"),
+            us_first_char_index: first,
+            us_last_char_index: last,
+            s_typo_ascender: ASCENT,
+            s_typo_descender: DESCENT,
+            us_win_ascent: ASCENT.max(bbox.y_max) as u16,
+            us_win_descent: (-DESCENT).max(-bbox.y_min) as u16,
+            ul_code_page_range_1: Some(1),
+            ul_code_page_range_2: Some(0),
+            sx_height: Some(0),
+            s_cap_height: Some(0),
+            us_default_char: Some(0),
+            us_break_char: Some(32),
+            us_max_context: Some(0),
+            ..Default::default()
+        };
+        let name = Name::new(
+            [
+                (NameId::FAMILY_NAME, self.family.clone()),
+                (NameId::SUBFAMILY_NAME, "Regular".into()),
+                (NameId::UNIQUE_ID, self.family.clone()),
+                (NameId::FULL_NAME, self.family.clone()),
+                (NameId::VERSION_STRING, "Version 1.0".into()),
+                (NameId::POSTSCRIPT_NAME, self.family.replace(' ', "")),
+            ]
+            .into_iter()
+            .map(|(id, s)| NameRecord::new(3, 1, 0x409, id, s.into()))
+            .collect(),
+        );
+        let post = Post::new_v2(names.iter().map(String::as_str));
+        let cmap = Cmap::from_mappings(mappings).map_err(|e| err(&e))?;
+        let hmtx = Hmtx::new(metrics, Vec::new());
+
+        let mut builder = FontBuilder::new();
+        builder
+            .add_table(&head)
+            .and_then(|b| b.add_table(&hhea))
+            .and_then(|b| b.add_table(&maxp))
+            .and_then(|b| b.add_table(&os2))
+            .and_then(|b| b.add_table(&hmtx))
+            .and_then(|b| b.add_table(&cmap))
+            .and_then(|b| b.add_table(&name))
+            .and_then(|b| b.add_table(&post))
+            .and_then(|b| b.add_table(&glyf))
+            .and_then(|b| b.add_table(&loca))
+            .map_err(|e| err(&e))?;
+        Ok(builder.build())
+    }
+
+    pub fn to_woff2(&self) -> Result<Vec<u8>, IconError> {
+        ttf_to_woff2(&self.to_ttf()?)
+    }
+
+    /// write `{family}.ttf`, `{family}.woff2`, `{family}.css` and
+    /// `{family}.json` into the directory, which is created if needed. The
+    /// codepoints of an existing `{family}.json` are kept, unless `codepoints`
+    /// gave others for the same icons.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let json = dir.join(format!("{}.json", self.family));
+        let mut builder = self.clone();
+        match fs::read_to_string(&json) {
+            Ok(previous) => {
+                for (id, ch) in parse_codepoints(&previous).map_err(invalid)? {
+                    builder.seed.entry(id).or_insert(ch);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let ttf = builder.to_ttf().map_err(invalid)?;
+        let woff2 = ttf_to_woff2(&ttf).map_err(invalid)?;
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format!("{}.ttf", self.family)), ttf)?;
+        fs::write(dir.join(format!("{}.woff2", self.family)), woff2)?;
+        fs::write(dir.join(format!("{}.css", self.family)), builder.to_css())?;
+        fs::write(json, builder.to_json())
+    }
+}
+
+/// the codepoints of a map written by `to_json`
+fn parse_codepoints(json: &str) -> Result<BTreeMap<String, char>, IconError> {
+    let map: BTreeMap<String, u32> = serde_json::from_str(json).map_err(|e| IconError::Parse {
+        pos: e.column(),
+        reason: "invalid codepoint map",
+    })?;
+    map.into_iter()
+        .map(|(id, cp)| {
+            let ch = char::from_u32(cp)
+                .filter(|_| (PUA_START..PUA_START + PUA_LEN).contains(&cp))
+                .ok_or(IconError::Parse {
+                    pos: 0,
+                    reason: "codepoint outside of the private use area",
+                })?;
+            Ok((id, ch))
+        })
+        .collect()
+}
+
+/// the outline of the icon in font units, with the icon centered on the em
+/// square between the descender and the ascender
+fn outline(icon: &Icon) -> Result<BezPath, IconError> {
+    let tree = usvg::Tree::from_str(&icon.to_xml()?, &usvg::Options::default())
+        .map_err(|e| IconError::Render(e.to_string()))?;
+    let size = tree.size();
+    let em = UNITS_PER_EM as f32;
+    let scale = em / size.width().max(size.height());
+    let dx = (em - size.width() * scale) / 2.0;
+    let dy = (em - size.height() * scale) / 2.0;
+    // the y axis of fonts points up
+    let ts = tiny_skia::Transform::from_row(scale, 0.0, 0.0, -scale, dx, ASCENT as f32 - dy);
+
+    let mut paths = Vec::new();
+    collect_paths(tree.root(), ts, &mut paths);
+    let mut outline = BezPath::new();
+    for (path, rule) in &paths {
+        let mut contours = contours(path);
+        if *rule == usvg::FillRule::EvenOdd {
+            to_nonzero(&mut contours);
+        }
+        for contour in contours {
+            outline.extend(contour.iter());
+        }
+    }
+    Ok(outline)
+}
+
+fn collect_paths(
+    group: &usvg::Group,
+    ts: tiny_skia::Transform,
+    out: &mut Vec<(tiny_skia::Path, usvg::FillRule)>,
+) {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => collect_paths(group, ts, out),
+            usvg::Node::Path(path) if path.is_visible() => {
+                let transform = path.abs_transform().post_concat(ts);
+                if let Some(fill) = path.fill() {
+                    let data = path.data().clone().transform(transform);
+                    out.extend(data.map(|data| (data, fill.rule())));
+                }
+                if let Some(stroke) = path.stroke() {
+                    let res_scale = tiny_skia::PathStroker::compute_resolution_scale(&transform);
+                    let data = path
+                        .data()
+                        .stroke(&stroke.to_tiny_skia(), res_scale)
+                        .and_then(|p| p.transform(transform));
+                    out.extend(data.map(|data| (data, usvg::FillRule::NonZero)));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// orient the contours so that the nonzero rule fills what the evenodd rule
+/// would: a contour nested in an odd number of others is a hole, and winds
+/// the other way. Contours which cross each other may still differ.
+fn to_nonzero(contours: &mut [BezPath]) {
+    let depths: Vec<usize> = contours
+        .iter()
+        .enumerate()
+        .map(|(i, contour)| {
+            let Some(&PathEl::MoveTo(start)) = contour.elements().first() else {
+                return 0;
+            };
+            contours
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && other.winding(start) != 0)
+                .count()
+        })
+        .collect();
+    for (contour, depth) in contours.iter_mut().zip(depths) {
+        if (contour.area() > 0.0) != (depth % 2 == 0) {
+            *contour = contour.reverse_subpaths();
+        }
+    }
+}
+
+/// the closed contours of the path, with cubic curves approximated by
+/// quadratic ones as TrueType requires
+fn contours(path: &tiny_skia::Path) -> Vec<BezPath> {
+    let pt = |p: tiny_skia::Point| Point::new(p.x as f64, p.y as f64);
+    let mut contours = Vec::new();
+    let mut contour = BezPath::new();
+    let mut flush = |contour: &mut BezPath| {
+        // a lone move draws nothing
+        if contour.elements().len() > 1 {
+            contour.close_path();
+            contours.push(std::mem::take(contour));
+        }
+        *contour = BezPath::new();
+    };
+
+    let mut last = Point::ZERO;
+    for segment in path.segments() {
+        match segment {
+            tiny_skia::PathSegment::MoveTo(p) => {
+                flush(&mut contour);
+                last = pt(p);
+                contour.move_to(last);
+            }
+            tiny_skia::PathSegment::LineTo(p) => {
+                last = pt(p);
+                contour.line_to(last);
+            }
+            tiny_skia::PathSegment::QuadTo(c, p) => {
+                last = pt(p);
+                contour.quad_to(pt(c), last);
+            }
+            tiny_skia::PathSegment::CubicTo(c1, c2, p) => {
+                let cubic = CubicBez::new(last, pt(c1), pt(c2), pt(p));
+                for (_, _, quad) in cubic.to_quads(1.0) {
+                    contour.quad_to(quad.p1, quad.p2);
+                }
+                last = pt(p);
+            }
+            tiny_skia::PathSegment::Close => flush(&mut contour),
+        }
+    }
+    flush(&mut contour);
+    contours
+}
+
+fn fnv1a(data: &[u8]) -> u32 {
+    data.iter().fold(0x811c_9dc5, |hash, b| {
+        (hash ^ *b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// the tags with a known index in WOFF2 table directories, up to `loca`
+const WOFF2_TAGS: &[&[u8; 4]] = &[
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca",
+];
+
+/// repackage a TrueType font as WOFF2, with the tables brotli compressed but
+/// not transformed
+fn ttf_to_woff2(ttf: &[u8]) -> Result<Vec<u8>, IconError> {
+    let invalid = || IconError::Render("invalid font".into());
+    let u16_at = |pos: usize| -> Option<u16> {
+        Some(u16::from_be_bytes(ttf.get(pos..pos + 2)?.try_into().ok()?))
+    };
+    let u32_at = |pos: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(ttf.get(pos..pos + 4)?.try_into().ok()?))
+    };
+
+    let num_tables = u16_at(4).ok_or_else(invalid)?;
+    let mut directory = Vec::new();
+    let mut stream = Vec::new();
+    let mut sfnt_size = 12 + 16 * num_tables as u32;
+    for i in 0..num_tables as usize {
+        let record = 12 + 16 * i;
+        let tag: [u8; 4] = ttf
+            .get(record..record + 4)
+            .and_then(|t| t.try_into().ok())
+            .ok_or_else(invalid)?;
+        let offset = u32_at(record + 8).ok_or_else(invalid)? as usize;
+        let length = u32_at(record + 12).ok_or_else(invalid)?;
+        let data = ttf
+            .get(offset..offset + length as usize)
+            .ok_or_else(invalid)?;
+
+        // glyf and loca use the null transform (3), the other tables 0
+        let transform = if &tag == b"glyf" || &tag == b"loca" {
+            0xc0
+        } else {
+            0
+        };
+        match WOFF2_TAGS.iter().position(|t| **t == tag) {
+            Some(index) => directory.push(index as u8 | transform),
+            None => {
+                directory.push(63 | transform);
+                directory.extend(tag);
+            }
+        }
+        write_base128(&mut directory, length);
+        stream.extend_from_slice(data);
+        sfnt_size += (length + 3) & !3;
+    }
+
+    let mut compressed = Vec::new();
+    let params = brotli::enc::BrotliEncoderParams {
+        quality: 11,
+        mode: brotli::enc::backward_references::BrotliEncoderMode::BROTLI_MODE_FONT,
+        ..Default::default()
+    };
+    brotli::BrotliCompress(&mut stream.as_slice(), &mut compressed, &params)
+        .map_err(|e| IconError::Render(e.to_string()))?;
+
+    let length = (48 + directory.len() + compressed.len() + 3) & !3;
+    let mut woff2 = Vec::with_capacity(length);
+    woff2.extend(b"wOF2");
+    woff2.extend(u32_at(0).ok_or_else(invalid)?.to_be_bytes());
+    woff2.extend((length as u32).to_be_bytes());
+    woff2.extend(num_tables.to_be_bytes());
+    woff2.extend([0, 0]);
+    woff2.extend(sfnt_size.to_be_bytes());
+    woff2.extend((compressed.len() as u32).to_be_bytes());
+    // version 1.0, no metadata nor private data
+    woff2.extend([0, 1, 0, 0]);
+    woff2.extend([0; 20]);
+    woff2.extend(directory);
+    woff2.extend(compressed);
+    woff2.resize(length, 0);
+    Ok(woff2)
+}
+
+/// the variable length `UIntBase128` of WOFF2
+fn write_base128(out: &mut Vec<u8>, value: u32) {
+    let mut bytes = vec![(value & 0x7f) as u8];
+    let mut value = value >> 7;
+    while value > 0 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    out.extend(bytes.iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_fonts::{FontRef, TableProvider};
+
+    #[test]
+    fn base128_should_work() {
+        let mut out = Vec::new();
+        write_base128(&mut out, 63);
+        write_base128(&mut out, 0x3fff);
+        write_base128(&mut out, 0x4000);
+        assert_eq!(out, [0x3f, 0xff, 0x7f, 0x81, 0x80, 0x00]);
+    }
+
+    #[cfg(all(feature = "heroicons", feature = "feather"))]
+    #[test]
+    fn icon_font_should_work() {
+        let mut builder = IconFontBuilder::new("app-icons");
+        builder
+            .add(&"heroicons:solid:trash".parse().unwrap())
+            .unwrap()
+            .add(&"feather:activity".parse().unwrap())
+            .unwrap();
+        let codepoints = builder.to_codepoints();
+        let trash = codepoints["heroicons:solid:trash"];
+        let activity = codepoints["feather:normal:activity"];
+
+        let ttf = builder.to_ttf().unwrap();
+        let font = FontRef::new(&ttf).unwrap();
+        assert_eq!(font.maxp().unwrap().num_glyphs(), 3);
+        let cmap = font.cmap().unwrap();
+        // glyphs are ordered by id
+        assert_eq!(cmap.map_codepoint(activity), Some(GlyphId::new(1)));
+        assert_eq!(cmap.map_codepoint(trash), Some(GlyphId::new(2)));
+        // the stroked icon is converted to an outline
+        let glyf = font.glyf().unwrap();
+        let loca = font.loca(None).unwrap();
+        assert!(loca.get_glyf(GlyphId::new(1), &glyf).unwrap().is_some());
+
+        let css = builder.to_css();
+        assert!(css.contains(&format!(
+            ".icon-heroicons-solid-trash::before {{\n  content: \"\\{:x}\";\n}}",
+            trash as u32
+        )));
+        assert!(builder
+            .to_json()
+            .contains(&format!("\"feather:normal:activity\": {}", activity as u32)));
+    }
+
+    #[cfg(all(feature = "heroicons", feature = "feather"))]
+    #[test]
+    fn codepoints_should_be_stable() {
+        let trash: Icon = "heroicons:solid:trash".parse().unwrap();
+        let mut first = IconFontBuilder::new("app-icons");
+        first.add(&trash).unwrap();
+        let json = first.to_json();
+
+        // a previously assigned codepoint is kept even if the hash changes
+        let taken = first.to_codepoints()["heroicons:solid:trash"];
+        let mut second = IconFontBuilder::new("app-icons")
+            .codepoints(&format!(
+                "{{\"feather:normal:activity\": {}}}",
+                taken as u32
+            ))
+            .unwrap();
+        second.add(&trash).unwrap();
+        assert_ne!(second.to_codepoints()["heroicons:solid:trash"], taken);
+
+        let mut third = IconFontBuilder::new("app-icons").codepoints(&json).unwrap();
+        third
+            .add(&"feather:activity".parse().unwrap())
+            .unwrap()
+            .add(&trash)
+            .unwrap();
+        assert_eq!(third.to_codepoints()["heroicons:solid:trash"], taken);
+        assert!(IconFontBuilder::new("x").codepoints("{\"a\": 65}").is_err());
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn adding_icons_should_not_move_codepoints() {
+        use crate::{Heroicons, IconType};
+
+        // two icons with the same hash, the second one sorting first
+        let mut homes = BTreeMap::new();
+        let (kept, colliding) = IconType::Heroicons(Heroicons::Outline)
+            .names()
+            .into_iter()
+            .map(|name| format!("heroicons:outline:{name}"))
+            .find_map(|id| {
+                let home = fnv1a(id.as_bytes()) % PUA_LEN;
+                homes.insert(home, id.clone()).map(|other| (id, other))
+            })
+            .unwrap();
+        let kept: Icon = kept.parse().unwrap();
+        let colliding: Icon = colliding.parse().unwrap();
+
+        let dir = std::env::temp_dir().join(format!("free-icons-font-{}", std::process::id()));
+        let mut first = IconFontBuilder::new("app-icons");
+        first.add(&kept).unwrap();
+        first.write_to(&dir).unwrap();
+        let codepoint = first.to_codepoints()[&kept.id()];
+
+        let mut second = IconFontBuilder::new("app-icons");
+        second.add(&kept).unwrap().add(&colliding).unwrap();
+        // without the previous map, the colliding icon takes the codepoint
+        assert_ne!(second.to_codepoints()[&kept.id()], codepoint);
+        second.write_to(&dir).unwrap();
+        let json = fs::read_to_string(dir.join("app-icons.json")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let codepoints = parse_codepoints(&json).unwrap();
+        assert_eq!(codepoints[&kept.id()], codepoint);
+        assert_ne!(codepoints[&colliding.id()], codepoint);
+    }
+
+    #[test]
+    fn evenodd_contours_should_become_holes() {
+        let square = |min: f64, max: f64| {
+            let mut path = BezPath::new();
+            path.move_to((min, min));
+            path.line_to((max, min));
+            path.line_to((max, max));
+            path.line_to((min, max));
+            path.close_path();
+            path
+        };
+        let mut contours = vec![square(0.0, 10.0), square(2.0, 8.0), square(4.0, 6.0)];
+        to_nonzero(&mut contours);
+        let winding =
+            |x: f64, y: f64| -> i32 { contours.iter().map(|c| c.winding(Point::new(x, y))).sum() };
+        assert_ne!(winding(1.0, 1.0), 0);
+        assert_eq!(winding(3.0, 3.0), 0);
+        assert_ne!(winding(5.0, 5.0), 0);
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn woff2_should_contain_the_font() {
+        let mut builder = IconFontBuilder::new("app-icons");
+        builder
+            .add(&"heroicons:solid:trash".parse().unwrap())
+            .unwrap();
+        let ttf = builder.to_ttf().unwrap();
+        let woff2 = ttf_to_woff2(&ttf).unwrap();
+        assert_eq!(&woff2[..4], b"wOF2");
+        assert_eq!(woff2.len() % 4, 0);
+        assert_eq!(
+            u32::from_be_bytes(woff2[8..12].try_into().unwrap()),
+            woff2.len() as u32
+        );
+
+        // the tables are stored in the order of the TrueType directory
+        let num_tables = u16::from_be_bytes(woff2[12..14].try_into().unwrap()) as usize;
+        let compressed_len = u32::from_be_bytes(woff2[20..24].try_into().unwrap()) as usize;
+        let mut pos = 48;
+        let mut lengths = Vec::new();
+        for _ in 0..num_tables {
+            let flags = woff2[pos];
+            pos += if flags & 63 == 63 { 5 } else { 1 };
+            let mut length = 0u32;
+            loop {
+                let b = woff2[pos];
+                pos += 1;
+                length = length << 7 | (b & 0x7f) as u32;
+                if b & 0x80 == 0 {
+                    break;
+                }
+            }
+            lengths.push(length as usize);
+        }
+        let mut stream = Vec::new();
+        brotli::BrotliDecompress(&mut &woff2[pos..pos + compressed_len], &mut stream).unwrap();
+        assert_eq!(stream.len(), lengths.iter().sum::<usize>());
+
+        let font = FontRef::new(&ttf).unwrap();
+        let mut offset = 0;
+        for (record, length) in font.table_directory.table_records().iter().zip(lengths) {
+            let table = font.table_data(record.tag()).unwrap();
+            assert_eq!(&stream[offset..offset + length], table.as_bytes());
+            offset += length;
+        }
+    }
+}
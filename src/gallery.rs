@@ -0,0 +1,264 @@
+use crate::{icon, Icon, IconType};
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+const STYLE: &str = r#"body{margin:0;font:14px/1.4 system-ui,sans-serif;color:#111827;background:#f9fafb}
+header{position:sticky;top:0;z-index:1;display:flex;flex-wrap:wrap;gap:.5rem 1rem;align-items:center;padding:.75rem 1.5rem;background:#fff;border-bottom:1px solid #e5e7eb}
+header h1{margin:0;font-size:1.125rem}header h1 a{color:inherit;text-decoration:none}
+header input{flex:1;min-width:12rem;padding:.4rem .6rem;border:1px solid #d1d5db;border-radius:.375rem;font:inherit}
+nav{display:flex;flex-wrap:wrap;gap:.25rem .75rem}nav a{color:#4f46e5;text-decoration:none}nav a.current{font-weight:600}
+main{padding:1rem 1.5rem}h2{font-size:1rem;margin:1.5rem 0 .75rem}
+.grid{display:grid;grid-template-columns:repeat(auto-fill,minmax(7.5rem,1fr));gap:.5rem}
+.icon{display:flex;flex-direction:column;align-items:center;gap:.5rem;padding:.75rem .25rem;border:1px solid #e5e7eb;border-radius:.5rem;background:#fff}
+.icon:hover{border-color:#4f46e5}.icon svg{width:2rem;height:2rem}
+.icon span{max-width:100%;overflow:hidden;text-overflow:ellipsis;white-space:nowrap;font-size:.75rem;color:#4b5563}
+.actions{display:flex;gap:.25rem}.actions button{padding:.125rem .375rem;border:1px solid #d1d5db;border-radius:.25rem;background:#f9fafb;font-size:.6875rem;cursor:pointer}
+.results a{display:block;padding:.125rem 0;color:#4f46e5;text-decoration:none}
+#toast{position:fixed;bottom:1rem;left:50%;transform:translateX(-50%);padding:.5rem 1rem;border-radius:.375rem;background:#111827;color:#fff;opacity:0;transition:opacity .2s}#toast.show{opacity:1}
+[hidden]{display:none!important}"#;
+
+// filter the cards of the page, and copy the snippets of the clicked button
+const SCRIPT: &str = r#"const input=document.getElementById("search"),toast=document.getElementById("toast");
+function show(text){toast.textContent=text;toast.classList.add("show");clearTimeout(show.timer);show.timer=setTimeout(()=>toast.classList.remove("show"),1200)}
+function copy(text,what){const done=()=>show("Copied "+what);if(navigator.clipboard&&window.isSecureContext){navigator.clipboard.writeText(text).then(done)}else{const area=document.createElement("textarea");area.value=text;document.body.appendChild(area);area.select();document.execCommand("copy");area.remove();done()}}
+input.addEventListener("input",()=>{const terms=input.value.toLowerCase().split(/\s+/).filter(Boolean);
+for(const card of document.querySelectorAll("[data-tags]")){card.hidden=!terms.every(t=>card.dataset.tags.includes(t))}
+for(const section of document.querySelectorAll("section")){section.hidden=!section.querySelector("[data-tags]:not([hidden])")}});
+document.addEventListener("click",e=>{const button=e.target.closest("button[data-copy]");if(!button)return;
+const card=button.closest(".icon");if(button.dataset.copy==="svg"){copy(card.querySelector("template").innerHTML,"SVG")}else{copy(card.dataset.rust,"Rust snippet")}});
+if(location.hash){input.value=decodeURIComponent(location.hash.slice(1));input.dispatchEvent(new Event("input"))}"#;
+
+/// generate a static HTML site to browse the bundled icons: an index with a
+/// search over all the icons, and a page with a grid per set and variant. The
+/// pages embed everything they need, so the site works offline.
+#[derive(Debug, Clone)]
+pub struct GalleryBuilder {
+    title: String,
+    types: Vec<IconType>,
+}
+
+impl Default for GalleryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GalleryBuilder {
+    /// a gallery of all the icon types enabled by the cargo features
+    pub fn new() -> Self {
+        Self {
+            title: "Free Icons".into(),
+            types: IconType::all(),
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// only include these icon types
+    pub fn types(mut self, types: impl IntoIterator<Item = IconType>) -> Self {
+        self.types = types.into_iter().collect();
+        self
+    }
+
+    /// the file name of the page of an icon type, e.g. `heroicons-outline.html`
+    pub fn page_name(icon_type: IconType) -> String {
+        format!("{}-{}.html", icon_type.set(), icon_type.variant())
+    }
+
+    /// the pages of the site, by file name
+    pub fn build(&self) -> BTreeMap<String, String> {
+        let mut pages = BTreeMap::new();
+        pages.insert("index.html".into(), self.index());
+        for &icon_type in &self.types {
+            pages.insert(Self::page_name(icon_type), self.page(icon_type));
+        }
+        pages
+    }
+
+    /// write the pages into the directory, which is created if needed
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (name, page) in self.build() {
+            fs::write(dir.join(name), page)?;
+        }
+        Ok(())
+    }
+
+    /// the list of the sets, and a search over the names of all the icons
+    /// which links to the cards in the pages
+    fn index(&self) -> String {
+        let mut main = String::from("<section><h2>Icon sets</h2><ul>");
+        for &icon_type in &self.types {
+            let _ = write!(
+                main,
+                "<li><a href=\"{}\">{icon_type}</a> ({} icons)</li>",
+                Self::page_name(icon_type),
+                icon_type.names().len()
+            );
+        }
+        main.push_str("</ul></section>");
+        for &icon_type in &self.types {
+            let page = Self::page_name(icon_type);
+            let _ = write!(
+                main,
+                "<section hidden><h2>{icon_type}</h2><div class=results>"
+            );
+            for icon in icon_type
+                .names()
+                .into_iter()
+                .filter_map(|name| icon(icon_type, name))
+            {
+                let name = icon.name();
+                let _ = write!(
+                    main,
+                    "<a href=\"{page}#{name}\" data-tags=\"{}\">{name}</a>",
+                    escape(&tags(&icon))
+                );
+            }
+            main.push_str("</div></section>");
+        }
+        // the results are only shown while searching
+        let script = r#"input.addEventListener("input",()=>{const searching=input.value.trim()!=="";const sets=document.querySelector("section");sets.hidden=searching;if(!searching)for(const s of document.querySelectorAll("section+section"))s.hidden=true})"#;
+        self.html(&self.title, None, &main, script)
+    }
+
+    fn page(&self, icon_type: IconType) -> String {
+        let mut main = format!("<section><h2>{icon_type}</h2><div class=grid>");
+        let rust_type = rust_type(icon_type);
+        for name in icon_type.names() {
+            let Some(icon) = icon(icon_type, name) else {
+                continue;
+            };
+            // the icons share the page, so their ids must not collide
+            let prefix = format!("{}-{}-{name}", icon_type.set(), icon_type.variant());
+            let body = icon.body();
+            let tags = tags(&icon);
+            let svg = icon.id_prefix(&prefix).to_string();
+            let rust = format!("free_icons::get({rust_type}, \"{name}\")");
+            let _ = write!(
+                main,
+                "<div class=icon id=\"{name}\" data-tags=\"{}\" data-rust=\"{}\">{svg}<span title=\"{name}\">{name}</span><template>{body}</template><div class=actions><button data-copy=rust>Rust</button><button data-copy=svg>SVG</button></div></div>",
+                escape(&tags),
+                escape(&rust),
+            );
+        }
+        main.push_str("</div></section>");
+        let title = format!("{icon_type} - {}", self.title);
+        self.html(&title, Some(icon_type), &main, "")
+    }
+
+    fn html(&self, title: &str, current: Option<IconType>, main: &str, script: &str) -> String {
+        let mut nav = String::new();
+        for &icon_type in &self.types {
+            let class = if Some(icon_type) == current {
+                " class=current"
+            } else {
+                ""
+            };
+            let _ = write!(
+                nav,
+                "<a href=\"{}\"{class}>{icon_type}</a>",
+                Self::page_name(icon_type)
+            );
+        }
+        format!(
+            "<!DOCTYPE html><html lang=en><head><meta charset=utf-8><meta name=viewport content=\"width=device-width,initial-scale=1\"><title>{}</title><style>{STYLE}</style></head><body><header><h1><a href=index.html>{}</a></h1><input id=search type=search placeholder=\"Search icons\" autofocus><nav>{nav}</nav></header><main>{main}</main><div id=toast></div><script>{SCRIPT}\n{script}</script></body></html>\n",
+            escape(title),
+            escape(&self.title),
+        )
+    }
+}
+
+/// the words to search an icon by: its name and its tags
+fn tags(icon: &Icon) -> String {
+    format!("{} {}", icon.name(), icon.tags().join(" "))
+}
+
+/// the Rust expression of the icon type, e.g. `IconType::Heroicons(Heroicons::Outline)`
+fn rust_type(icon_type: IconType) -> String {
+    let set = pascal_case(icon_type.set());
+    let variant = pascal_case(icon_type.variant());
+    format!("IconType::{set}({set}::{variant})")
+}
+
+/// `font-awesome` -> `FontAwesome`, like the names of the enums
+fn pascal_case(s: &str) -> String {
+    s.split('-')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn gallery_should_work() {
+        use crate::Heroicons;
+
+        let outline = IconType::Heroicons(Heroicons::Outline);
+        assert_eq!(
+            rust_type(outline),
+            "IconType::Heroicons(Heroicons::Outline)"
+        );
+
+        let pages = GalleryBuilder::new()
+            .title("Intranet <Icons>")
+            .types([outline])
+            .build();
+        assert_eq!(
+            pages.keys().collect::<Vec<_>>(),
+            ["heroicons-outline.html", "index.html"]
+        );
+
+        let index = &pages["index.html"];
+        assert!(index.contains("<title>Intranet &lt;Icons&gt;</title>"));
+        assert!(index.contains("<a href=\"heroicons-outline.html#trash\" data-tags=\"trash heroicons outline trash\">trash</a>"));
+
+        let page = &pages["heroicons-outline.html"];
+        assert!(page.contains("<div class=icon id=\"trash\" data-tags=\"trash heroicons outline trash\" data-rust=\"free_icons::get(IconType::Heroicons(Heroicons::Outline), &quot;trash&quot;)\"><svg "));
+        let body = crate::get(outline, "trash").unwrap();
+        assert!(page.contains(&format!("<template>{body}</template>")));
+        assert_eq!(
+            page.matches("<div class=icon ").count(),
+            outline.names().len()
+        );
+        // no external resources
+        assert!(!page.contains("src=") && !page.contains("<link"));
+    }
+
+    #[cfg(all(feature = "ionicons", feature = "font-awesome"))]
+    #[test]
+    fn gallery_ids_and_snippets_should_be_valid() {
+        use crate::{FontAwesome, Ionicons};
+
+        assert_eq!(
+            rust_type(IconType::FontAwesome(FontAwesome::Solid)),
+            "IconType::FontAwesome(FontAwesome::Solid)"
+        );
+        let pages = GalleryBuilder::new()
+            .types([IconType::Ionicons(Ionicons::Normal)])
+            .build();
+        let page = &pages["ionicons-normal.html"];
+        assert!(page.contains(" id=ionicons-normal-balloon-icons "));
+        assert!(!page.contains("balloon--"));
+    }
+}
@@ -10,3 +10,8 @@ pub mod heroicons;
 pub mod ionicons;
 #[cfg(feature = "octicons")]
 pub mod octicons;
+
+// the sorted names of the icons of the enabled sets, written by build.rs
+pub mod names {
+    include!(concat!(env!("OUT_DIR"), "/names.rs"));
+}
@@ -0,0 +1,283 @@
+use crate::{
+    render_to_io, Animation, AnimationMode, ElementStyle, IconAttrs, IconDocument, IconError,
+    IconType, Selector, ViewBox,
+};
+use std::{borrow::Cow, fmt, io, str::FromStr};
+
+/// a handle to a bundled icon, with the attributes to render it with. It
+/// renders by streaming the attributes between `<svg` and the cached body,
+/// without intermediate strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Icon<'a> {
+    icon_type: IconType,
+    name: &'static str,
+    body: &'static str,
+    attrs: IconAttrs<'a>,
+}
+
+/// what we know about an icon without rendering it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IconMeta {
+    pub view_box: Option<ViewBox>,
+    /// whether the icon defines ids, see `IconAttrs::unique_ids`
+    pub has_ids: bool,
+}
+
+/// look up an icon, to render it with `Display` or `render_to`
+pub fn icon(icon_type: IconType, name: &str) -> Option<Icon<'static>> {
+    icon_type
+        .icons()
+        .get_key_value(name)
+        .map(|(name, body)| Icon {
+            icon_type,
+            name,
+            body,
+            attrs: IconAttrs::default(),
+        })
+}
+
+/// find the icons whose ids contain every word of the query, e.g. `arrow left`
+/// or `heroicons trash`. Exact names come first, then the names starting with
+/// the query, then the shorter names.
+pub fn search(query: &str) -> Vec<Icon<'static>> {
+    let query = query.to_lowercase();
+    let terms: Vec<_> = query.split_whitespace().collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let joined = terms.join("-");
+    let mut found: Vec<_> = IconType::all()
+        .into_iter()
+        .flat_map(|icon_type| {
+            icon_type
+                .names()
+                .into_iter()
+                .map(move |name| (icon_type, name))
+        })
+        .filter(|(icon_type, name)| {
+            let id = format!("{icon_type}:{name}");
+            terms.iter().all(|term| id.contains(term))
+        })
+        .filter_map(|(icon_type, name)| icon(icon_type, name))
+        .collect();
+    found.sort_by_cached_key(|icon| {
+        (
+            icon.name != joined,
+            !icon.name.starts_with(&joined),
+            icon.name.len(),
+            icon.id(),
+        )
+    });
+    found
+}
+
+impl<'a> Icon<'a> {
+    pub fn icon_type(&self) -> IconType {
+        self.icon_type
+    }
+
+    /// the name of the icon set, e.g. `heroicons`
+    pub fn set(&self) -> &'static str {
+        self.icon_type.set()
+    }
+
+    /// the name of the variant, e.g. `outline`
+    pub fn variant(&self) -> &'static str {
+        self.icon_type.variant()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// the canonical id of the icon: `set:variant:name`
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.icon_type, self.name)
+    }
+
+    /// the words to find the icon by: its set, its variant and the words of
+    /// its name
+    pub fn tags(&self) -> Vec<&'static str> {
+        let mut tags = vec![self.set(), self.variant()];
+        for word in self.name.split('-').filter(|word| word.len() > 1) {
+            if !tags.contains(&word) {
+                tags.push(word);
+            }
+        }
+        tags
+    }
+
+    /// the cached SVG, without the attributes
+    pub fn body(&self) -> &'static str {
+        self.body
+    }
+
+    pub fn meta(&self) -> IconMeta {
+        IconMeta {
+            view_box: IconDocument::parse(self.body)
+                .ok()
+                .and_then(|doc| doc.view_box()),
+            has_ids: crate::has_ids(self.body),
+        }
+    }
+
+    pub fn get_attrs(&self) -> &IconAttrs<'a> {
+        &self.attrs
+    }
+
+    /// render the icon with other attributes
+    pub fn attrs<'b>(self, attrs: IconAttrs<'b>) -> Icon<'b> {
+        Icon {
+            icon_type: self.icon_type,
+            name: self.name,
+            body: self.body,
+            attrs,
+        }
+    }
+
+    #[inline(always)]
+    pub fn class(self, class: &'a str) -> Self {
+        self.map(|attrs| attrs.class(class))
+    }
+
+    #[inline(always)]
+    pub fn fill(self, fill: &'a str) -> Self {
+        self.map(|attrs| attrs.fill(fill))
+    }
+
+    #[inline(always)]
+    pub fn stroke_color(self, stroke_color: &'a str) -> Self {
+        self.map(|attrs| attrs.stroke_color(stroke_color))
+    }
+
+    #[inline(always)]
+    pub fn stroke_width(self, stroke_width: &'a str) -> Self {
+        self.map(|attrs| attrs.stroke_width(stroke_width))
+    }
+
+    #[inline(always)]
+    pub fn with(self, attr: &'a str, value: &'a str) -> Self {
+        self.map(|attrs| attrs.with(attr, value))
+    }
+
+    #[inline(always)]
+    pub fn id_prefix(self, prefix: &'a str) -> Self {
+        self.map(|attrs| attrs.id_prefix(prefix))
+    }
+
+    #[inline(always)]
+    pub fn unique_ids(self) -> Self {
+        self.map(|attrs| attrs.unique_ids())
+    }
+
+    #[inline(always)]
+    pub fn style_element(self, selector: impl Into<Selector<'a>>, style: ElementStyle<'a>) -> Self {
+        self.map(|attrs| attrs.style_element(selector, style))
+    }
+
+    #[inline(always)]
+    pub fn duotone(self, primary: &'a str, secondary: &'a str) -> Self {
+        self.map(|attrs| attrs.duotone(primary, secondary))
+    }
+
+    #[inline(always)]
+    pub fn animate(self, animation: impl Into<Animation>) -> Self {
+        self.map(|attrs| attrs.animate(animation))
+    }
+
+    #[inline(always)]
+    pub fn animation_mode(self, mode: AnimationMode) -> Self {
+        self.map(|attrs| attrs.animation_mode(mode))
+    }
+
+    #[inline(always)]
+    pub fn respect_reduced_motion(self) -> Self {
+        self.map(|attrs| attrs.respect_reduced_motion())
+    }
+
+    pub fn render_to(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        self.attrs.render_to(w, self.body)
+    }
+
+    pub fn render_to_io(&self, w: &mut impl io::Write) -> io::Result<()> {
+        render_to_io(w, self.body, &self.attrs)
+    }
+
+    /// the rendered icon as a document, for further editing. The attributes of
+    /// the icon replace the ones of its root rather than being added.
+    pub fn to_document(&self) -> Result<IconDocument, IconError> {
+        self.to_document_with(std::iter::empty::<(&str, &str)>())
+    }
+
+    /// like `to_document`, with more attributes replacing the ones of the root,
+    /// so that it stays valid XML
+    pub fn to_document_with<K, V>(
+        &self,
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<IconDocument, IconError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut doc = IconDocument::parse(&self.attrs.transform(self.body)?)?;
+        let root = doc.root_mut();
+        for (name, value) in self.attrs.pairs() {
+            root.set_attr(name, value);
+        }
+        for (name, value) in attrs {
+            root.set_attr(name, value);
+        }
+        Ok(doc)
+    }
+
+    /// the rendered icon as a standalone XML document, e.g. for `.svg` files
+    pub fn to_xml(&self) -> Result<String, IconError> {
+        Ok(self.to_document()?.to_xml())
+    }
+
+    #[inline(always)]
+    fn map(mut self, f: impl FnOnce(IconAttrs<'a>) -> IconAttrs<'a>) -> Self {
+        self.attrs = f(self.attrs);
+        self
+    }
+}
+
+impl fmt::Display for Icon<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+/// look up an icon by its canonical id, `set:variant:name`. The variant can
+/// be omitted for the sets with a single variant, e.g. `feather:activity`.
+impl FromStr for Icon<'_> {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (icon_type, name) = s
+            .rsplit_once(':')
+            .ok_or_else(|| IconError::UnknownIconType(s.to_owned()))?;
+        let icon_type = icon_type.parse()?;
+        icon(icon_type, name).ok_or_else(|| IconError::NotFound(s.to_owned()))
+    }
+}
+
+impl From<Icon<'_>> for String {
+    fn from(icon: Icon<'_>) -> Self {
+        let mut out = String::with_capacity(icon.body.len() + icon.attrs.len_hint());
+        // writing into a String never fails
+        let _ = icon.render_to(&mut out);
+        out
+    }
+}
+
+/// borrow the cached body if there is nothing to render
+impl From<Icon<'_>> for Cow<'static, str> {
+    fn from(icon: Icon<'_>) -> Self {
+        if icon.attrs == IconAttrs::default() {
+            Cow::Borrowed(icon.body)
+        } else {
+            Cow::Owned(icon.into())
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// how the ids of an icon are rewritten when it is rendered
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum IdPrefix<'a> {
+    #[default]
+    None,
+    Fixed(&'a str),
+    Auto,
+}
+
+impl IdPrefix<'_> {
+    pub(crate) fn apply<'s>(&self, svg: &'s str) -> Cow<'s, str> {
+        match self {
+            IdPrefix::None => Cow::Borrowed(svg),
+            IdPrefix::Fixed(prefix) => rewrite_ids(svg, prefix),
+            IdPrefix::Auto if has_ids(svg) => rewrite_ids(svg, &next_id_prefix()),
+            IdPrefix::Auto => Cow::Borrowed(svg),
+        }
+    }
+}
+
+/// generate a process-wide unique prefix (`fi0`, `fi1`, ...) for id rewriting
+pub fn next_id_prefix() -> String {
+    format!("fi{}", COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// check if the SVG defines any `id` attribute
+pub fn has_ids(svg: &str) -> bool {
+    svg.contains("id=") && !collect_ids(svg).is_empty()
+}
+
+/// prefix every `id` in the SVG with `{prefix}-`, and update the `url(#...)` and
+/// `href="#..."` references to them. The SVG is borrowed as is if it has no ids.
+pub fn rewrite_ids<'a>(svg: &'a str, prefix: &str) -> Cow<'a, str> {
+    if !svg.contains("id=") {
+        return Cow::Borrowed(svg);
+    }
+
+    let ids = collect_ids(svg);
+    if ids.is_empty() {
+        return Cow::Borrowed(svg);
+    }
+
+    let mut out = String::with_capacity(svg.len() + ids.len() * (prefix.len() + 1) * 2);
+    let mut last = 0;
+    for attr in Attributes::new(svg) {
+        let value = &svg[attr.value.clone()];
+        if let Some(rewritten) = rewrite_attr(attr.name, value, &ids, prefix) {
+            out.push_str(&svg[last..attr.value.start]);
+            out.push_str(&rewritten);
+            last = attr.value.end;
+        }
+    }
+    out.push_str(&svg[last..]);
+
+    Cow::Owned(out)
+}
+
+/// the value of the attribute with the `ids` prefixed, or `None` if it does
+/// not refer to any of them
+pub(crate) fn rewrite_attr(
+    name: &str,
+    value: &str,
+    ids: &HashSet<&str>,
+    prefix: &str,
+) -> Option<String> {
+    match name {
+        "id" => Some(format!("{prefix}-{value}")),
+        "href" | "xlink:href" => value
+            .strip_prefix('#')
+            .filter(|id| ids.contains(id))
+            .map(|id| format!("#{prefix}-{id}")),
+        _ => rewrite_urls(value, ids, prefix),
+    }
+}
+
+fn collect_ids(svg: &str) -> HashSet<&str> {
+    Attributes::new(svg)
+        .filter(|attr| attr.name == "id")
+        .map(|attr| &svg[attr.value])
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+fn rewrite_urls(value: &str, ids: &HashSet<&str>, prefix: &str) -> Option<String> {
+    if !value.contains("url(#") {
+        return None;
+    }
+
+    let mut out = String::with_capacity(value.len() + prefix.len() + 1);
+    let mut rest = value;
+    let mut changed = false;
+    while let Some(pos) = rest.find("url(#") {
+        let (head, tail) = rest.split_at(pos + 5);
+        out.push_str(head);
+        let end = tail.find(')').unwrap_or(tail.len());
+        let id = &tail[..end];
+        if ids.contains(id) {
+            out.push_str(prefix);
+            out.push('-');
+            changed = true;
+        }
+        out.push_str(id);
+        rest = &tail[end..];
+    }
+    out.push_str(rest);
+
+    changed.then_some(out)
+}
+
+/// an attribute of a start tag, with the byte range of its (unquoted) value
+#[derive(Debug)]
+struct Attribute<'a> {
+    name: &'a str,
+    value: std::ops::Range<usize>,
+}
+
+/// iterate over the attributes of all start tags in a (minified) SVG
+struct Attributes<'a> {
+    svg: &'a str,
+    pos: usize,
+    in_tag: bool,
+}
+
+impl<'a> Attributes<'a> {
+    fn new(svg: &'a str) -> Self {
+        Self {
+            svg,
+            pos: 0,
+            in_tag: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = Attribute<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.svg.as_bytes();
+        loop {
+            if !self.in_tag {
+                let start = self.pos + self.svg[self.pos..].find('<')?;
+                let name_len = bytes[start + 1..]
+                    .iter()
+                    .take_while(|b| !b.is_ascii_whitespace() && !matches!(b, b'>' | b'/'))
+                    .count();
+                self.pos = start + 1 + name_len;
+                self.in_tag = bytes.get(start + 1).is_some_and(u8::is_ascii_alphabetic);
+                continue;
+            }
+
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            match bytes.get(self.pos)? {
+                b'>' => {
+                    self.in_tag = false;
+                    self.pos += 1;
+                    continue;
+                }
+                b'/' => {
+                    self.pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let name_start = self.pos;
+            while self.pos < bytes.len()
+                && !bytes[self.pos].is_ascii_whitespace()
+                && !matches!(bytes[self.pos], b'=' | b'>' | b'/')
+            {
+                self.pos += 1;
+            }
+            let name = &self.svg[name_start..self.pos];
+
+            if bytes.get(self.pos) != Some(&b'=') {
+                return Some(Attribute {
+                    name,
+                    value: self.pos..self.pos,
+                });
+            }
+            self.pos += 1;
+
+            let value = match bytes.get(self.pos) {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let start = self.pos + 1;
+                    let end = self.svg[start..]
+                        .find(quote as char)
+                        .map_or(bytes.len(), |n| start + n);
+                    self.pos = (end + 1).min(bytes.len());
+                    start..end
+                }
+                _ => {
+                    let start = self.pos;
+                    while self.pos < bytes.len()
+                        && !bytes[self.pos].is_ascii_whitespace()
+                        && bytes[self.pos] != b'>'
+                    {
+                        self.pos += 1;
+                    }
+                    start..self.pos
+                }
+            };
+
+            return Some(Attribute { name, value });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRADIENT: &str = r##"<svg viewbox="0 0 24 24" xmlns=http://www.w3.org/2000/svg><defs><linearGradient id=a><stop offset=0 /></linearGradient><clipPath id="b"><rect height=24 width=24 /></clipPath></defs><g clip-path=url(#b)><path d="M0 0h24v24H0z" fill="url(#a)"/><use href="#a" /><use xlink:href=#c /></g></svg>"##;
+
+    #[test]
+    fn rewrite_ids_should_prefix_ids_and_references() {
+        assert_eq!(
+            rewrite_ids(GRADIENT, "x1"),
+            r##"<svg viewbox="0 0 24 24" xmlns=http://www.w3.org/2000/svg><defs><linearGradient id=x1-a><stop offset=0 /></linearGradient><clipPath id="x1-b"><rect height=24 width=24 /></clipPath></defs><g clip-path=url(#x1-b)><path d="M0 0h24v24H0z" fill="url(#x1-a)"/><use href="#x1-a" /><use xlink:href=#c /></g></svg>"##
+        );
+    }
+
+    #[test]
+    fn rewrite_ids_should_borrow_svg_without_ids() {
+        let svg = r#"<svg data-id=x viewbox="0 0 16 16"><path d="M0 0h16"/></svg>"#;
+        assert!(!has_ids(svg));
+        assert!(matches!(rewrite_ids(svg, "x1"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn auto_prefix_should_be_unique() {
+        let first = IdPrefix::Auto.apply(GRADIENT);
+        let second = IdPrefix::Auto.apply(GRADIENT);
+        assert_ne!(first, second);
+    }
+}
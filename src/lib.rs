@@ -1,13 +1,108 @@
 use flate2::bufread::GzDecoder;
-use std::{borrow::Cow, collections::HashMap, io::Read};
+use std::{borrow::Cow, collections::HashMap, fmt, io::Read, str::FromStr};
 
+mod animate;
+#[cfg(feature = "server")]
+mod api;
+#[cfg(feature = "cdylib")]
+mod capi;
+#[cfg(any(feature = "dioxus", feature = "leptos", feature = "yew"))]
+mod components;
+mod compose;
+mod convert;
+mod css;
+mod data_uri;
+mod document;
+mod error;
+#[cfg(feature = "raster")]
+mod favicon;
+#[cfg(feature = "font")]
+mod font;
+mod gallery;
 mod gen;
+mod icon;
+mod ids;
+#[cfg(feature = "raster")]
+mod raster;
+mod render;
+#[cfg(feature = "server")]
+mod server;
+mod sprite;
+mod style;
+#[cfg(any(
+    feature = "askama",
+    feature = "hypertext",
+    feature = "markup",
+    feature = "maud",
+    feature = "minijinja",
+    feature = "tera"
+))]
+mod template;
+#[cfg(feature = "raster")]
+mod terminal;
+mod tsx;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use animate::AnimationSettings;
+use ids::IdPrefix;
+
+pub use animate::{Animation, AnimationMode, Pulse, Shake, Spin};
+#[cfg(feature = "server")]
+pub use api::api_router;
+#[cfg(feature = "dioxus")]
+pub use components::dioxus;
+#[cfg(feature = "leptos")]
+pub use components::leptos;
+#[cfg(feature = "yew")]
+pub use components::yew;
+pub use compose::{Composition, Overlay, Position};
+pub use convert::Converted;
+pub use css::{ClassNaming, CssBuilder};
+pub use data_uri::{svg_base64_data_uri, svg_data_uri};
+pub use document::{Element, IconDocument, Node, ViewBox};
+pub use error::IconError;
+#[cfg(feature = "raster")]
+pub use favicon::{FaviconBuilder, FaviconFile};
+#[cfg(feature = "font")]
+pub use font::IconFontBuilder;
+pub use gallery::GalleryBuilder;
+pub use icon::{icon, search, Icon, IconMeta};
+pub use ids::{has_ids, next_id_prefix, rewrite_ids};
+#[cfg(feature = "raster")]
+pub use raster::{rasterize_svg, RgbaImage};
+pub use render::{render_to, render_to_io};
+#[cfg(feature = "server")]
+pub use server::icon_router;
+pub use sprite::SpriteBuilder;
+pub use style::{ElementStyle, Selector};
+#[cfg(feature = "askama")]
+pub use template::askama_filters;
+#[cfg(any(
+    feature = "askama",
+    feature = "hypertext",
+    feature = "markup",
+    feature = "maud",
+    feature = "minijinja",
+    feature = "tera"
+))]
+pub use template::render_icon;
+#[cfg(feature = "minijinja")]
+pub use template::{add_minijinja_icons, minijinja_icon};
+#[cfg(feature = "tera")]
+pub use template::{add_tera_icons, TeraIcon};
+#[cfg(feature = "raster")]
+pub use terminal::{TerminalMode, TerminalRenderer};
+pub use tsx::TsxBuilder;
 
 const MAX_ATTRS: usize = 16;
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct IconAttrs<'a> {
     data: [(&'a str, Cow<'a, str>); MAX_ATTRS],
     pos: u8,
+    ids: IdPrefix<'a>,
+    styles: Vec<(Selector<'a>, ElementStyle<'a>)>,
+    animation: AnimationSettings,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -67,39 +162,279 @@ pub enum Octicons {
     Normal,
 }
 
-/// retrieve the SVG from incon_type and name
-pub fn get(icon_type: IconType, name: &str) -> Option<&'static String> {
-    match icon_type {
+impl IconType {
+    /// all the icon sets and variants enabled by the cargo features
+    pub fn all() -> Vec<IconType> {
+        #[allow(unused_mut)]
+        let mut all = Vec::new();
         #[cfg(feature = "bootstrap")]
-        IconType::Bootstrap(icon_type) => match icon_type {
-            Bootstrap::Fill => gen::bootstrap::FILL.get(name),
-            Bootstrap::Normal => gen::bootstrap::NORMAL.get(name),
-        },
+        all.extend([Bootstrap::Fill, Bootstrap::Normal].map(IconType::Bootstrap));
         #[cfg(feature = "feather")]
-        IconType::Feather(icon_type) => match icon_type {
-            Feather::Normal => gen::feather::NORMAL.get(name),
-        },
+        all.push(IconType::Feather(Feather::Normal));
         #[cfg(feature = "font-awesome")]
-        IconType::FontAwesome(icon_type) => match icon_type {
-            FontAwesome::Regular => gen::font_awesome::REGULAR.get(name),
-            FontAwesome::Solid => gen::font_awesome::SOLID.get(name),
-        },
+        all.extend([FontAwesome::Regular, FontAwesome::Solid].map(IconType::FontAwesome));
         #[cfg(feature = "heroicons")]
-        IconType::Heroicons(icon_type) => match icon_type {
-            Heroicons::Outline => gen::heroicons::OUTLINE.get(name),
-            Heroicons::Solid => gen::heroicons::SOLID.get(name),
-        },
+        all.extend([Heroicons::Outline, Heroicons::Solid].map(IconType::Heroicons));
         #[cfg(feature = "ionicons")]
-        IconType::Ionicons(icon_type) => match icon_type {
-            Ionicons::Outline => gen::ionicons::OUTLINE.get(name),
-            Ionicons::Sharp => gen::ionicons::SHARP.get(name),
-            Ionicons::Normal => gen::ionicons::NORMAL.get(name),
-        },
+        all.extend([Ionicons::Outline, Ionicons::Sharp, Ionicons::Normal].map(IconType::Ionicons));
         #[cfg(feature = "octicons")]
-        IconType::Octicons(icon_type) => match icon_type {
-            Octicons::Normal => gen::octicons::NORMAL.get(name),
-        },
+        all.push(IconType::Octicons(Octicons::Normal));
+        all
+    }
+
+    /// the name of the icon set, e.g. `heroicons`
+    pub fn set(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "bootstrap")]
+            IconType::Bootstrap(_) => "bootstrap",
+            #[cfg(feature = "feather")]
+            IconType::Feather(_) => "feather",
+            #[cfg(feature = "font-awesome")]
+            IconType::FontAwesome(_) => "font-awesome",
+            #[cfg(feature = "heroicons")]
+            IconType::Heroicons(_) => "heroicons",
+            #[cfg(feature = "ionicons")]
+            IconType::Ionicons(_) => "ionicons",
+            #[cfg(feature = "octicons")]
+            IconType::Octicons(_) => "octicons",
+        }
+    }
+
+    /// the name of the variant in the set, e.g. `outline`
+    pub fn variant(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "bootstrap")]
+            IconType::Bootstrap(Bootstrap::Fill) => "fill",
+            #[cfg(feature = "font-awesome")]
+            IconType::FontAwesome(FontAwesome::Regular) => "regular",
+            #[cfg(feature = "font-awesome")]
+            IconType::FontAwesome(FontAwesome::Solid) => "solid",
+            #[cfg(feature = "heroicons")]
+            IconType::Heroicons(Heroicons::Outline) => "outline",
+            #[cfg(feature = "heroicons")]
+            IconType::Heroicons(Heroicons::Solid) => "solid",
+            #[cfg(feature = "ionicons")]
+            IconType::Ionicons(Ionicons::Outline) => "outline",
+            #[cfg(feature = "ionicons")]
+            IconType::Ionicons(Ionicons::Sharp) => "sharp",
+            #[allow(unreachable_patterns)]
+            _ => "normal",
+        }
+    }
+
+    /// the icon types of `set:variant`, or all the variants of `set`
+    pub fn matching(s: &str) -> Result<Vec<IconType>, IconError> {
+        if let Ok(icon_type) = s.parse() {
+            return Ok(vec![icon_type]);
+        }
+        let types: Vec<_> = Self::all().into_iter().filter(|t| t.set() == s).collect();
+        if types.is_empty() {
+            return Err(IconError::UnknownIconType(s.to_owned()));
+        }
+        Ok(types)
+    }
+
+    /// the names of the icons of this type, sorted
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.icons().keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// whether there is an icon with the name, in constants as well, for
+    /// `icon!` to check the names at compile time
+    #[doc(hidden)]
+    pub const fn has_icon(self, name: &str) -> bool {
+        contains(self.sorted_names(), name)
+    }
+
+    const fn sorted_names(self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "bootstrap")]
+            IconType::Bootstrap(icon_type) => match icon_type {
+                Bootstrap::Fill => gen::names::bootstrap::FILL,
+                Bootstrap::Normal => gen::names::bootstrap::NORMAL,
+            },
+            #[cfg(feature = "feather")]
+            IconType::Feather(icon_type) => match icon_type {
+                Feather::Normal => gen::names::feather::NORMAL,
+            },
+            #[cfg(feature = "font-awesome")]
+            IconType::FontAwesome(icon_type) => match icon_type {
+                FontAwesome::Regular => gen::names::font_awesome::REGULAR,
+                FontAwesome::Solid => gen::names::font_awesome::SOLID,
+            },
+            #[cfg(feature = "heroicons")]
+            IconType::Heroicons(icon_type) => match icon_type {
+                Heroicons::Outline => gen::names::heroicons::OUTLINE,
+                Heroicons::Solid => gen::names::heroicons::SOLID,
+            },
+            #[cfg(feature = "ionicons")]
+            IconType::Ionicons(icon_type) => match icon_type {
+                Ionicons::Outline => gen::names::ionicons::OUTLINE,
+                Ionicons::Sharp => gen::names::ionicons::SHARP,
+                Ionicons::Normal => gen::names::ionicons::NORMAL,
+            },
+            #[cfg(feature = "octicons")]
+            IconType::Octicons(icon_type) => match icon_type {
+                Octicons::Normal => gen::names::octicons::NORMAL,
+            },
+        }
     }
+
+    fn icons(&self) -> &'static HashMap<String, String> {
+        match *self {
+            #[cfg(feature = "bootstrap")]
+            IconType::Bootstrap(icon_type) => match icon_type {
+                Bootstrap::Fill => &gen::bootstrap::FILL,
+                Bootstrap::Normal => &gen::bootstrap::NORMAL,
+            },
+            #[cfg(feature = "feather")]
+            IconType::Feather(icon_type) => match icon_type {
+                Feather::Normal => &gen::feather::NORMAL,
+            },
+            #[cfg(feature = "font-awesome")]
+            IconType::FontAwesome(icon_type) => match icon_type {
+                FontAwesome::Regular => &gen::font_awesome::REGULAR,
+                FontAwesome::Solid => &gen::font_awesome::SOLID,
+            },
+            #[cfg(feature = "heroicons")]
+            IconType::Heroicons(icon_type) => match icon_type {
+                Heroicons::Outline => &gen::heroicons::OUTLINE,
+                Heroicons::Solid => &gen::heroicons::SOLID,
+            },
+            #[cfg(feature = "ionicons")]
+            IconType::Ionicons(icon_type) => match icon_type {
+                Ionicons::Outline => &gen::ionicons::OUTLINE,
+                Ionicons::Sharp => &gen::ionicons::SHARP,
+                Ionicons::Normal => &gen::ionicons::NORMAL,
+            },
+            #[cfg(feature = "octicons")]
+            IconType::Octicons(icon_type) => match icon_type {
+                Octicons::Normal => &gen::octicons::NORMAL,
+            },
+        }
+    }
+}
+
+/// `set:variant`, e.g. `heroicons:outline`
+impl fmt::Display for IconType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.set(), self.variant())
+    }
+}
+
+/// parse `set:variant`, or just `set` for the sets with a single variant
+impl FromStr for IconType {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (set, variant) = s.split_once(':').unwrap_or((s, ""));
+        let mut candidates = Self::all().into_iter().filter(|t| t.set() == set);
+        let found = if variant.is_empty() {
+            match (candidates.next(), candidates.next()) {
+                (Some(t), None) => Some(t),
+                _ => None,
+            }
+        } else {
+            candidates.find(|t| t.variant() == variant)
+        };
+        found.ok_or_else(|| IconError::UnknownIconType(s.to_owned()))
+    }
+}
+
+/// `names.contains(&name)`, in constants as well
+const fn contains(names: &[&str], name: &str) -> bool {
+    let name = name.as_bytes();
+    let mut i = 0;
+    'names: while i < names.len() {
+        let candidate = names[i].as_bytes();
+        i += 1;
+        if candidate.len() != name.len() {
+            continue;
+        }
+        let mut j = 0;
+        while j < name.len() {
+            if candidate[j] != name[j] {
+                continue 'names;
+            }
+            j += 1;
+        }
+        return true;
+    }
+    false
+}
+
+/// look up a bundled icon by variant and name, e.g.
+/// `icon!(heroicons::Outline, "trash").class("h-4")`, or with the type of the
+/// set, `icon!(Heroicons::Outline, "trash")`. A name written as a literal is
+/// checked at compile time; any other name panics if there is no such icon,
+/// see `try_icon!` for those.
+#[macro_export]
+macro_rules! icon {
+    (bootstrap :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(Bootstrap::$variant, $($name)+)
+    };
+    (feather :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(Feather::$variant, $($name)+)
+    };
+    (font_awesome :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(FontAwesome::$variant, $($name)+)
+    };
+    (heroicons :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(Heroicons::$variant, $($name)+)
+    };
+    (ionicons :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(Ionicons::$variant, $($name)+)
+    };
+    (octicons :: $variant:ident, $($name:tt)+) => {
+        $crate::icon!(Octicons::$variant, $($name)+)
+    };
+    ($set:ident :: $variant:ident, $name:literal) => {{
+        const _: () = assert!(
+            $crate::IconType::$set($crate::$set::$variant).has_icon($name),
+            concat!("icon not found: ", $name)
+        );
+        $crate::icon($crate::IconType::$set($crate::$set::$variant), $name)
+            .expect("checked at compile time")
+    }};
+    ($set:ident :: $variant:ident, $name:expr) => {{
+        let name = $name;
+        $crate::icon($crate::IconType::$set($crate::$set::$variant), name)
+            .unwrap_or_else(|| panic!("icon not found: {}", name))
+    }};
+}
+
+/// like `icon!`, but `None` if there is no such icon, e.g. for names which
+/// come from the data of a template
+#[macro_export]
+macro_rules! try_icon {
+    (bootstrap :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(Bootstrap::$variant, $($name)+)
+    };
+    (feather :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(Feather::$variant, $($name)+)
+    };
+    (font_awesome :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(FontAwesome::$variant, $($name)+)
+    };
+    (heroicons :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(Heroicons::$variant, $($name)+)
+    };
+    (ionicons :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(Ionicons::$variant, $($name)+)
+    };
+    (octicons :: $variant:ident, $($name:tt)+) => {
+        $crate::try_icon!(Octicons::$variant, $($name)+)
+    };
+    ($set:ident :: $variant:ident, $name:expr) => {
+        $crate::icon($crate::IconType::$set($crate::$set::$variant), $name)
+    };
+}
+
+/// retrieve the SVG from incon_type and name
+pub fn get(icon_type: IconType, name: &str) -> Option<&'static String> {
+    icon_type.icons().get(name)
 }
 
 #[cfg(feature = "bootstrap")]
@@ -189,31 +524,96 @@ impl<'a> IconAttrs<'a> {
         self.with("stroke-width", stroke_width)
     }
 
+    /// prefix every `id` (and the references to it) with `{prefix}-`, so that
+    /// several copies of an icon using gradients, clip paths or masks can be
+    /// inlined into the same page
     #[inline(always)]
-    pub fn with(self, attr: &'a str, value: &'a str) -> Self {
-        let mut data = self.data;
-        let mut pos = self.pos;
-        data[pos as usize] = (attr, value.into());
-        pos = (pos + 1) % MAX_ATTRS as u8;
+    pub fn id_prefix(mut self, prefix: &'a str) -> Self {
+        self.ids = IdPrefix::Fixed(prefix);
+        self
+    }
 
-        Self { data, pos }
+    /// like `id_prefix`, but use a process-wide counter (`fi0`, `fi1`, ...) to
+    /// generate a fresh prefix for every render of an icon which has ids
+    #[inline(always)]
+    pub fn unique_ids(mut self) -> Self {
+        self.ids = IdPrefix::Auto;
+        self
     }
-    fn add_to_svg(&self, svg: Option<&String>) -> Option<String> {
-        if let Some(svg) = svg {
-            let mut svg = svg.to_owned();
-            let mut attrs = String::new();
-            for i in 0..self.pos {
-                let (k, v) = &self.data[i as usize];
-                attrs.push_str(&format!(" {k}=\"{v}\""));
-            }
 
-            if !attrs.is_empty() {
-                svg.insert_str(4, &attrs);
-            }
-            Some(svg)
-        } else {
-            None
+    /// style the elements matching the selector, e.g. `Selector::parse("path:nth(1)")?`
+    /// or an index
+    #[inline(always)]
+    pub fn style_element(
+        mut self,
+        selector: impl Into<Selector<'a>>,
+        style: ElementStyle<'a>,
+    ) -> Self {
+        self.styles.push((selector.into(), style));
+        self
+    }
+
+    /// tint the first shape with the primary color, and the others with the
+    /// secondary color at 40% opacity
+    #[inline(always)]
+    pub fn duotone(self, primary: &'a str, secondary: &'a str) -> Self {
+        let secondary = ElementStyle::default().color(secondary).opacity(0.4);
+        let primary = ElementStyle::default().color(primary).opacity(1.0);
+        self.style_element(Selector::All, secondary)
+            .style_element(Selector::Index(0), primary)
+    }
+
+    /// animate the icon, e.g. `animate(Spin { duration })`
+    #[inline(always)]
+    pub fn animate(mut self, animation: impl Into<Animation>) -> Self {
+        self.animation.animation = Some(animation.into());
+        self
+    }
+
+    /// embed the animation as CSS (the default) or SMIL
+    #[inline(always)]
+    pub fn animation_mode(mut self, mode: AnimationMode) -> Self {
+        self.animation.mode = mode;
+        self
+    }
+
+    /// only play the animation if the user has not asked the system to
+    /// minimize motion (`prefers-reduced-motion`)
+    #[inline(always)]
+    pub fn respect_reduced_motion(mut self) -> Self {
+        self.animation.reduced_motion = true;
+        self
+    }
+
+    #[inline(always)]
+    pub fn with(mut self, attr: &'a str, value: &'a str) -> Self {
+        self.data[self.pos as usize] = (attr, value.into());
+        self.pos = (self.pos + 1) % MAX_ATTRS as u8;
+        self
+    }
+
+    /// apply the changes which need to rewrite the body of the icon. The
+    /// element styles and animations need to parse it, so it fails rather than
+    /// dropping them if the body is not valid.
+    fn transform<'s>(&self, svg: &'s str) -> Result<Cow<'s, str>, IconError> {
+        let svg = self.ids.apply(svg);
+        if self.styles.is_empty() && self.animation.animation.is_none() {
+            return Ok(svg);
+        }
+
+        let mut doc = IconDocument::parse(&svg)?;
+        for (selector, style) in &self.styles {
+            doc.style(selector, style);
         }
+        self.animation.apply(&mut doc);
+        Ok(Cow::Owned(doc.to_string()))
+    }
+
+    fn add_to_svg(&self, svg: Option<&String>) -> Option<String> {
+        let svg = svg?;
+        let mut out = String::with_capacity(svg.len() + self.len_hint());
+        self.render_to(&mut out, svg).ok()?;
+        Some(out)
     }
 }
 
@@ -548,6 +948,212 @@ mod tests {
         assert_eq!(&icon[..32], "<svg class=\"h-8 w-8 text-white\" ");
     }
 
+    #[cfg(feature = "ionicons")]
+    #[test]
+    fn unique_ids_should_work() {
+        let attrs = IconAttrs::default().id_prefix("logo");
+        let icon = ionicons("logo-android", Ionicons::Normal, attrs).expect("exists");
+        assert!(icon.contains(" id=logo-path80319 "));
+
+        let first = ionicons("prism", Ionicons::Normal, IconAttrs::default().unique_ids());
+        let second = ionicons("prism", Ionicons::Normal, IconAttrs::default().unique_ids());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn icon_document_should_round_trip_bundled_icons() {
+        let mut maps: Vec<&HashMap<String, String>> = Vec::new();
+        #[cfg(feature = "bootstrap")]
+        maps.extend([*gen::bootstrap::FILL, *gen::bootstrap::NORMAL]);
+        #[cfg(feature = "feather")]
+        maps.push(*gen::feather::NORMAL);
+        #[cfg(feature = "font-awesome")]
+        maps.extend([*gen::font_awesome::REGULAR, *gen::font_awesome::SOLID]);
+        #[cfg(feature = "heroicons")]
+        maps.extend([*gen::heroicons::OUTLINE, *gen::heroicons::SOLID]);
+        #[cfg(feature = "ionicons")]
+        maps.extend([
+            *gen::ionicons::OUTLINE,
+            *gen::ionicons::SHARP,
+            *gen::ionicons::NORMAL,
+        ]);
+        #[cfg(feature = "octicons")]
+        maps.push(*gen::octicons::NORMAL);
+
+        for svg in maps.into_iter().flat_map(|m| m.values()) {
+            let doc = IconDocument::parse(svg).expect("should parse");
+            assert_eq!(&doc.to_string(), svg);
+            assert!(doc.view_box().is_some());
+        }
+    }
+
+    #[cfg(feature = "ionicons")]
+    #[test]
+    fn duotone_should_work() {
+        let attrs = IconAttrs::default().duotone("#1e40af", "#93c5fd");
+        let icon = ionicons("accessibility", Ionicons::Normal, attrs).expect("exists");
+        let doc = IconDocument::parse(&icon).unwrap();
+        let paths: Vec<_> = doc.root().elements().collect();
+        assert_eq!(paths[0].attr("fill"), Some("#1e40af"));
+        assert_eq!(paths[1].attr("opacity"), Some("0.4"));
+    }
+
+    #[cfg(all(feature = "heroicons", feature = "ionicons"))]
+    #[test]
+    fn composition_should_work() {
+        let bell = get(IconType::Heroicons(Heroicons::Outline), "bell").expect("exists");
+        let prism = get(IconType::Ionicons(Ionicons::Normal), "prism").expect("exists");
+        let doc = Composition::new(bell)
+            .overlay(Overlay::new(prism).knockout(1.0))
+            .overlay(Overlay::new(prism).position(Position::TopLeft))
+            .render()
+            .expect("should compose");
+
+        let mut ids = Vec::new();
+        doc.root().visit(&mut |el| ids.extend(el.attr("id")));
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+        assert_eq!(doc.root().elements().count(), 4);
+    }
+
+    #[cfg(feature = "ionicons")]
+    #[test]
+    fn animation_should_work() {
+        let spin = Spin {
+            duration: std::time::Duration::from_secs(2),
+        };
+        let attrs = IconAttrs::default()
+            .class("h-4")
+            .animate(spin)
+            .animation_mode(AnimationMode::Smil);
+        let icon = ionicons("sync", Ionicons::Normal, attrs).expect("exists");
+        assert!(icon.starts_with("<svg class=\"h-4\" "));
+        assert!(icon.contains("<animateTransform attributeName=transform type=rotate from=\"0 256 256\" to=\"360 256 256\" dur=2000ms repeatCount=indefinite /></g></svg>"));
+    }
+
+    #[cfg(feature = "bootstrap")]
+    #[test]
+    fn icon_display_should_work() {
+        let attrs = IconAttrs::default().class("h-8 w-8 text-white");
+        let icon = icon(IconType::Bootstrap(Bootstrap::Normal), "alarm")
+            .expect("exists")
+            .attrs(attrs.clone());
+        assert_eq!(Some(icon.to_string()), bootstrap("alarm", false, attrs));
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn icon_handle_should_work() {
+        let icon: Icon = "heroicons:outline:academic-cap".parse().expect("exists");
+        assert_eq!(icon.icon_type(), IconType::Heroicons(Heroicons::Outline));
+        assert_eq!(icon.name(), "academic-cap");
+        assert_eq!(icon.id(), "heroicons:outline:academic-cap");
+        assert_eq!(icon.meta().view_box.map(|v| v.width), Some(24.0));
+        assert!(matches!(Cow::from(icon.clone()), Cow::Borrowed(_)));
+
+        let styled = icon.clone().class("h-4");
+        assert_ne!(styled, icon);
+        assert!(String::from(styled).starts_with("<svg class=\"h-4\" "));
+
+        assert_eq!(
+            "heroicons:outline:not-exist".parse::<Icon>(),
+            Err(IconError::NotFound("heroicons:outline:not-exist".into()))
+        );
+        assert!("heroicons:trash".parse::<Icon>().is_err());
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn icon_macros_should_work() {
+        let trash = icon(IconType::Heroicons(Heroicons::Outline), "trash");
+        assert_eq!(Some(crate::icon!(heroicons::Outline, "trash")), trash);
+        assert_eq!(Some(crate::icon!(Heroicons::Outline, "trash")), trash);
+        let name = String::from("trash");
+        assert_eq!(Some(crate::icon!(heroicons::Outline, name.as_str())), trash);
+        assert_eq!(crate::try_icon!(heroicons::Outline, &name), trash);
+        assert_eq!(crate::try_icon!(Heroicons::Outline, "nope"), None);
+
+        for icon_type in IconType::all() {
+            for name in icon_type.names() {
+                assert!(icon_type.has_icon(name));
+            }
+            assert!(!icon_type.has_icon("nope"));
+        }
+    }
+
+    #[cfg(feature = "feather")]
+    #[test]
+    fn icon_document_should_replace_root_attributes() {
+        let icon: Icon = "feather:activity".parse().expect("exists");
+        let doc = icon
+            .stroke_color("red")
+            .stroke_width("3")
+            .to_document_with([("class", "h-4"), ("stroke-width", "4")])
+            .unwrap();
+        let root = doc.root();
+        for name in ["stroke", "stroke-width", "class"] {
+            assert_eq!(root.attrs().filter(|(k, _)| *k == name).count(), 1);
+        }
+        assert_eq!(root.attr("stroke"), Some("red"));
+        assert_eq!(root.attr("stroke-width"), Some("4"));
+        assert_eq!(root.attr("class"), Some("h-4"));
+    }
+
+    #[cfg(feature = "feather")]
+    #[test]
+    fn data_uri_should_work() {
+        let icon: Icon = "feather:activity".parse().expect("exists");
+        let uri = icon.clone().stroke_color("#f00").to_data_uri().unwrap();
+        assert!(uri.starts_with("data:image/svg+xml,%3Csvg viewBox='0 0 24 24' "));
+        assert_eq!(uri.matches(" stroke=").count(), 1);
+        assert!(uri.contains(" stroke='%23f00' "));
+        assert!(!uri.contains('"'));
+
+        let uri = icon.to_base64_data_uri().unwrap();
+        assert!(uri.starts_with("data:image/svg+xml;base64,PHN2Zy"));
+    }
+
+    #[test]
+    fn icon_type_should_round_trip() {
+        for icon_type in IconType::all() {
+            assert_eq!(icon_type.to_string().parse::<IconType>(), Ok(icon_type));
+        }
+        assert_eq!(
+            "feather".parse::<IconType>(),
+            Ok(IconType::Feather(Feather::Normal))
+        );
+        assert_eq!(
+            IconType::matching("feather"),
+            Ok(vec![IconType::Feather(Feather::Normal)])
+        );
+        assert!(IconType::matching("nope").is_err());
+    }
+
+    #[cfg(all(feature = "heroicons", feature = "feather"))]
+    #[test]
+    fn search_should_rank_exact_names_first() {
+        let ids: Vec<_> = search("Trash").iter().map(Icon::id).collect();
+        let exact = ids.iter().take_while(|id| id.ends_with(":trash")).count();
+        assert!(exact >= 3 && ids[exact..].iter().all(|id| !id.ends_with(":trash")));
+        assert!(ids.contains(&"heroicons:solid:trash".to_string()));
+        assert!(ids.iter().all(|id| id.contains("trash")));
+
+        let ids: Vec<_> = search("heroicons arrow left")
+            .iter()
+            .map(Icon::id)
+            .collect();
+        assert_eq!(ids[0], "heroicons:outline:arrow-left");
+        assert!(ids
+            .iter()
+            .all(|id| id.starts_with("heroicons:") && id.contains("left")));
+        assert!(search(" ").is_empty());
+
+        let names = IconType::Feather(Feather::Normal).names();
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn icon_should_not_exist() {
         assert_eq!(get(IconType::Feather(Feather::Normal), "not_exist"), None);
@@ -0,0 +1,183 @@
+use crate::{Icon, IconError};
+use resvg::{tiny_skia, usvg};
+
+/// a rasterized icon: RGBA pixels, row by row, without premultiplied alpha
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Icon<'_> {
+    /// render the icon (with its attributes) into a square of `size * scale`
+    /// pixels, keeping its aspect ratio. `currentColor` is black unless a
+    /// `color` attribute is set, e.g. `icon.with("color", "#0f172a")`.
+    pub fn rasterize(&self, size: u32, scale: f32) -> Result<RgbaImage, IconError> {
+        let side = (size as f32 * scale).round() as u32;
+        rasterize_svg(&self.to_xml()?, side, side)
+    }
+
+    /// like `rasterize`, but encode the image as PNG
+    pub fn to_png(&self, size: u32, scale: f32) -> Result<Vec<u8>, IconError> {
+        self.rasterize(size, scale)?.to_png()
+    }
+}
+
+impl RgbaImage {
+    pub fn to_png(&self) -> Result<Vec<u8>, IconError> {
+        self.to_pixmap()?
+            .encode_png()
+            .map_err(|e| IconError::Render(e.to_string()))
+    }
+
+    pub fn from_png(data: &[u8]) -> Result<Self, IconError> {
+        let pixmap =
+            tiny_skia::Pixmap::decode_png(data).map_err(|e| IconError::Render(e.to_string()))?;
+        Ok(Self::from_pixmap(&pixmap))
+    }
+
+    pub(crate) fn to_pixmap(&self) -> Result<tiny_skia::Pixmap, IconError> {
+        let data = self
+            .data
+            .chunks_exact(4)
+            .flat_map(|px| {
+                let c = tiny_skia::ColorU8::from_rgba(px[0], px[1], px[2], px[3]).premultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect();
+        let size = tiny_skia::IntSize::from_wh(self.width, self.height)
+            .ok_or_else(|| IconError::Render("empty image".into()))?;
+        tiny_skia::Pixmap::from_vec(data, size)
+            .ok_or_else(|| IconError::Render("invalid image".into()))
+    }
+
+    pub(crate) fn from_pixmap(pixmap: &tiny_skia::Pixmap) -> Self {
+        let data = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|px| {
+                let c = px.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect();
+        Self {
+            width: pixmap.width(),
+            height: pixmap.height(),
+            data,
+        }
+    }
+}
+
+/// render an SVG document, centered into `width * height` pixels
+pub fn rasterize_svg(svg: &str, width: u32, height: u32) -> Result<RgbaImage, IconError> {
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default())
+        .map_err(|e| IconError::Render(e.to_string()))?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| IconError::Render("empty image".into()))?;
+
+    let size = tree.size();
+    let scale = (width as f32 / size.width()).min(height as f32 / size.height());
+    let dx = (width as f32 - size.width() * scale) / 2.0;
+    let dy = (height as f32 - size.height() * scale) / 2.0;
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(dx, dy);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    Ok(RgbaImage::from_pixmap(&pixmap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &[(&str, u32, f32, &[u8])] = &[
+        #[cfg(feature = "bootstrap")]
+        (
+            "bootstrap:fill:alarm",
+            32,
+            1.0,
+            include_bytes!("../tests/fixtures/raster/bootstrap-fill-alarm-32.png"),
+        ),
+        #[cfg(feature = "feather")]
+        (
+            "feather:activity",
+            24,
+            2.0,
+            include_bytes!("../tests/fixtures/raster/feather-normal-activity-24@2x.png"),
+        ),
+        #[cfg(feature = "font-awesome")]
+        (
+            "font-awesome:solid:github",
+            32,
+            1.0,
+            include_bytes!("../tests/fixtures/raster/font-awesome-solid-github-32.png"),
+        ),
+    ];
+
+    #[test]
+    fn rasterize_should_match_reference_images() {
+        for (id, size, scale, png) in FIXTURES {
+            let icon: Icon = id.parse().unwrap();
+            let actual = icon.rasterize(*size, *scale).unwrap();
+            let expected = RgbaImage::from_png(png).unwrap();
+            assert_eq!(
+                (actual.width, actual.height),
+                (expected.width, expected.height)
+            );
+
+            let diff: u64 = actual
+                .data
+                .iter()
+                .zip(&expected.data)
+                .map(|(a, b)| a.abs_diff(*b) as u64)
+                .sum();
+            let mean = diff as f64 / actual.data.len() as f64;
+            assert!(
+                mean < 1.0,
+                "{id} differs from the reference image by {mean}"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "bootstrap", feature = "feather", feature = "heroicons"))]
+    #[test]
+    fn rasterize_should_apply_overridden_attributes() {
+        let opaque = |image: &RgbaImage| -> Vec<[u8; 3]> {
+            image
+                .data
+                .chunks_exact(4)
+                .filter(|px| px[3] == 255)
+                .map(|px| [px[0], px[1], px[2]])
+                .collect()
+        };
+        for (id, attr, value) in [
+            ("bootstrap:fill:alarm", "fill", "#f00"),
+            ("feather:activity", "stroke", "#f00"),
+        ] {
+            let icon: Icon = id.parse().unwrap();
+            let pixels = opaque(&icon.with(attr, value).rasterize(32, 2.0).unwrap());
+            assert!(!pixels.is_empty());
+            assert!(pixels.iter().all(|px| *px == [255, 0, 0]), "{id}");
+        }
+
+        let coverage =
+            |image: RgbaImage| -> u64 { image.data.chunks_exact(4).map(|px| px[3] as u64).sum() };
+        let trash: Icon = "heroicons:outline:trash".parse().unwrap();
+        let thin = coverage(trash.rasterize(24, 2.0).unwrap());
+        let thick = coverage(trash.stroke_width("3").rasterize(24, 2.0).unwrap());
+        assert!(thick > thin);
+    }
+
+    #[test]
+    fn png_should_round_trip() {
+        let svg = r##"<svg viewBox="0 0 2 1" xmlns="http://www.w3.org/2000/svg"><rect width="1" height="1" fill="#f00" fill-opacity="0.5"/></svg>"##;
+        let image = rasterize_svg(svg, 4, 4).unwrap();
+        // centered vertically
+        assert_eq!(&image.data[..4], &[0, 0, 0, 0]);
+        assert_eq!(&image.data[16..20], &[255, 0, 0, 128]);
+        assert_eq!(
+            RgbaImage::from_png(&image.to_png().unwrap()).unwrap(),
+            image
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::IconAttrs;
+use std::{fmt, io};
+
+/// write the SVG with the attributes into `w`. Nothing is allocated unless the
+/// attributes need to rewrite the body (ids, element styles or animations).
+/// It fails if the element styles or animations cannot parse the body.
+pub fn render_to(w: &mut impl fmt::Write, svg: &str, attrs: &IconAttrs) -> fmt::Result {
+    attrs.render_to(w, svg)
+}
+
+/// like `render_to`, but write into an `io::Write`
+pub fn render_to_io(w: &mut impl io::Write, svg: &str, attrs: &IconAttrs) -> io::Result<()> {
+    let mut adapter = IoAdapter {
+        inner: w,
+        error: None,
+    };
+    match attrs.render_to(&mut adapter, svg) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(adapter.error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "the svg cannot be parsed")
+        })),
+    }
+}
+
+impl IconAttrs<'_> {
+    pub(crate) fn render_to(&self, w: &mut impl fmt::Write, svg: &str) -> fmt::Result {
+        self.render_with(w, svg, false)
+    }
+
+    /// like `render_to`, with the values of the attributes escaped for HTML
+    #[cfg(any(feature = "hypertext", feature = "markup", feature = "maud"))]
+    pub(crate) fn render_html_to(&self, w: &mut impl fmt::Write, svg: &str) -> fmt::Result {
+        self.render_with(w, svg, true)
+    }
+
+    fn render_with(&self, w: &mut impl fmt::Write, svg: &str, escape: bool) -> fmt::Result {
+        let svg = self.transform(svg).map_err(|_| fmt::Error)?;
+        // skip the xml declaration if there is one
+        let pos = svg.find("<svg").map_or(0, |n| n + 4);
+        w.write_str(&svg[..pos])?;
+        self.write_attrs_with(w, escape)?;
+        w.write_str(&svg[pos..])
+    }
+
+    /// write the attributes, each with a leading space
+    pub(crate) fn write_attrs(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        self.write_attrs_with(w, false)
+    }
+
+    fn write_attrs_with(&self, w: &mut impl fmt::Write, escape: bool) -> fmt::Result {
+        for (k, v) in self.pairs() {
+            w.write_char(' ')?;
+            w.write_str(k)?;
+            w.write_str("=\"")?;
+            if escape {
+                write_escaped(w, v)?;
+            } else {
+                w.write_str(v)?;
+            }
+            w.write_char('"')?;
+        }
+        Ok(())
+    }
+
+    /// the attributes, in the order they were added
+    pub(crate) fn pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.data[..self.pos as usize]
+            .iter()
+            .map(|(k, v)| (*k, v.as_ref()))
+    }
+
+    /// the length of the rendered attributes, to size the output buffer
+    pub(crate) fn len_hint(&self) -> usize {
+        self.pairs().map(|(k, v)| k.len() + v.len() + 4).sum()
+    }
+}
+
+/// write the string with `&`, `"`, `<` and `>` replaced by entities
+pub(crate) fn write_escaped(w: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    let mut last = 0;
+    for (i, c) in s.char_indices() {
+        let entity = match c {
+            '&' => "&amp;",
+            '"' => "&quot;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            _ => continue,
+        };
+        w.write_str(&s[last..i])?;
+        w.write_str(entity)?;
+        last = i + 1;
+    }
+    w.write_str(&s[last..])
+}
+
+/// the attribute with its name checked and its value escaped, for the
+/// attributes given by templates or other languages, which are set on the root
+/// as they are
+#[cfg(any(
+    feature = "askama",
+    feature = "cdylib",
+    feature = "hypertext",
+    feature = "markup",
+    feature = "maud",
+    feature = "minijinja",
+    feature = "tera",
+    feature = "wasm"
+))]
+pub(crate) fn escaped_attr(name: &str, value: &str) -> Result<(String, String), crate::IconError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'));
+    if !valid {
+        return Err(crate::IconError::Render(format!(
+            "invalid attribute name: {name}"
+        )));
+    }
+    let mut escaped = String::with_capacity(value.len());
+    // writing into a String never fails
+    let _ = write_escaped(&mut escaped, value);
+    Ok((name.to_owned(), escaped))
+}
+
+struct IoAdapter<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<?xml version="1.0"?><svg viewbox="0 0 24 24"><path d="M12 3v18"/></svg>"#;
+
+    #[test]
+    fn render_to_should_stream_attributes() {
+        let attrs = IconAttrs::default().class("h-4").fill("none");
+        let mut out = String::new();
+        render_to(&mut out, SVG, &attrs).unwrap();
+        assert_eq!(
+            out,
+            r#"<?xml version="1.0"?><svg class="h-4" fill="none" viewbox="0 0 24 24"><path d="M12 3v18"/></svg>"#
+        );
+
+        let mut buf = Vec::new();
+        render_to_io(&mut buf, SVG, &attrs).unwrap();
+        assert_eq!(buf, out.as_bytes());
+    }
+
+    #[test]
+    fn invalid_svg_should_fail_to_apply_styles() {
+        let svg = r#"<svg viewbox="0 0 24 24"><path d="M12 3v18"></svg>"#;
+        let attrs = IconAttrs::default().class("h-4");
+        let mut out = String::new();
+        render_to(&mut out, svg, &attrs).unwrap();
+        assert!(out.starts_with("<svg class=\"h-4\" "));
+
+        let attrs = attrs.duotone("red", "blue");
+        assert!(render_to(&mut String::new(), svg, &attrs).is_err());
+        let err = render_to_io(&mut Vec::new(), svg, &attrs).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(attrs.transform(svg).is_err());
+    }
+
+    #[test]
+    fn render_to_io_should_return_io_errors() {
+        let mut buf = [0u8; 8];
+        let err = render_to_io(&mut &mut buf[..], SVG, &IconAttrs::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+}
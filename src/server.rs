@@ -0,0 +1,429 @@
+use crate::{icon, Icon, IconAttrs, IconType};
+use axum::{
+    extract::{Path, Query},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use flate2::{write::GzEncoder, Compression};
+use std::io::Write;
+
+type Validate = fn(&str) -> bool;
+
+/// the query parameters accepted by the icon route, and the attributes they
+/// set on the `<svg>`. `size` sets both the width and the height.
+const PARAMS: &[(&str, &[&str], Validate)] = &[
+    ("size", &["width", "height"], is_length),
+    ("width", &["width"], is_length),
+    ("height", &["height"], is_length),
+    ("color", &["color"], is_color),
+    ("fill", &["fill"], is_color),
+    ("stroke", &["stroke"], is_color),
+    ("stroke-width", &["stroke-width"], is_length),
+];
+
+/// a router serving the icons at `/icons/{set}/{variant}/{name}.svg`, e.g.
+/// `/icons/heroicons/outline/trash.svg?size=24&color=%23f00`. The responses
+/// can be cached forever: the content only changes with the crate version,
+/// and the strong ETag changes with it.
+pub fn icon_router() -> Router {
+    Router::new().route("/icons/{set}/{variant}/{file}", get(serve_icon))
+}
+
+async fn serve_icon(
+    Path((set, variant, file)): Path<(String, String, String)>,
+    Query(query): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(name) = file.strip_suffix(".svg") else {
+        return not_found(&format!("{set}:{variant}:{file}"), None, &file);
+    };
+    let Ok(icon_type) = format!("{set}:{variant}").parse::<IconType>() else {
+        return not_found(&format!("{set}:{variant}:{name}"), None, name);
+    };
+    let Some(icon) = icon(icon_type, name) else {
+        return not_found(&format!("{icon_type}:{name}"), Some(icon_type), name);
+    };
+
+    let attrs = match parse_attrs(&query) {
+        Ok(attrs) => attrs,
+        Err(reason) => return (StatusCode::BAD_REQUEST, reason).into_response(),
+    };
+    let svg = match icon.to_document_with(attrs.pairs()) {
+        Ok(doc) => doc.to_xml(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let encoding = negotiate(&headers);
+    let etag = format!("\"{:016x}{}\"", fnv1a(svg.as_bytes()), encoding.suffix());
+    let mut response = if is_fresh(&headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        encoding.encode(svg.into_bytes()).into_response()
+    };
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("image/svg+xml"),
+    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    headers.insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+    if let Some(value) = encoding.header() {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(value));
+    }
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    response
+}
+
+/// validate the query parameters, so that only well-formed values end up in
+/// the attributes
+fn parse_attrs(query: &[(String, String)]) -> Result<IconAttrs<'_>, String> {
+    let mut attrs = IconAttrs::default();
+    for (key, value) in query {
+        let Some((_, names, valid)) = PARAMS.iter().find(|(param, ..)| param == key) else {
+            let params: Vec<_> = PARAMS.iter().map(|(param, ..)| *param).collect();
+            return Err(format!(
+                "unknown parameter `{key}`, expected one of: {}",
+                params.join(", ")
+            ));
+        };
+        if !valid(value) {
+            return Err(format!("invalid value for `{key}`: {value:?}"));
+        }
+        for name in *names {
+            attrs = attrs.with(name, value);
+        }
+    }
+    Ok(attrs)
+}
+
+/// a number with an optional CSS unit, e.g. `24`, `1.5` or `2em`
+fn is_length(s: &str) -> bool {
+    let number = s.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%');
+    let unit = &s[number.len()..];
+    matches!(unit, "" | "px" | "em" | "rem" | "%")
+        && number
+            .parse::<f32>()
+            .is_ok_and(|n| n.is_finite() && n >= 0.0)
+}
+
+/// a hex color (`#f00`, `#ff000080`), a named color or `currentColor`
+fn is_color(s: &str) -> bool {
+    match s.strip_prefix('#') {
+        Some(hex) => {
+            matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => !s.is_empty() && s.len() <= 32 && s.chars().all(|c| c.is_ascii_alphabetic()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+impl Encoding {
+    fn header(self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Brotli => Some("br"),
+        }
+    }
+
+    /// each encoding is a different representation, with its own ETag
+    fn suffix(self) -> &'static str {
+        match self {
+            Encoding::Identity => "",
+            Encoding::Gzip => "-gz",
+            Encoding::Brotli => "-br",
+        }
+    }
+
+    /// compress on the fly, for every request: the levels are cheap ones, the
+    /// best ones cost far more CPU for a few bytes on small icons
+    fn encode(self, data: Vec<u8>) -> Vec<u8> {
+        match self {
+            Encoding::Identity => data,
+            Encoding::Gzip => {
+                let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+                // writing into a Vec never fails
+                let _ = gz.write_all(&data);
+                gz.finish().unwrap_or_default()
+            }
+            Encoding::Brotli => {
+                let mut out = Vec::new();
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality: 5,
+                    ..Default::default()
+                };
+                let _ = brotli::BrotliCompress(&mut data.as_slice(), &mut out, &params);
+                out
+            }
+        }
+    }
+}
+
+/// the preferred encoding of `Accept-Encoding`: brotli, then gzip, unless the
+/// client prefers another one with a higher `q`
+fn negotiate(headers: &HeaderMap) -> Encoding {
+    let mut best = (Encoding::Identity, 0.0);
+    let accepted = headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','));
+    for item in accepted {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or_default().trim();
+        let q = parts
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        let encoding = match coding.to_ascii_lowercase().as_str() {
+            "br" => Encoding::Brotli,
+            "gzip" | "x-gzip" => Encoding::Gzip,
+            _ => continue,
+        };
+        let better = q > best.1 || (q == best.1 && encoding == Encoding::Brotli);
+        if q > 0.0 && better {
+            best = (encoding, q);
+        }
+    }
+    best.0
+}
+
+/// whether the client already has the representation, from `If-None-Match`
+fn is_fresh(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// a 404 listing the closest icons, e.g. `trash` for `trahs`, or the icon
+/// types if the set or the variant is unknown
+fn not_found(id: &str, icon_type: Option<IconType>, name: &str) -> Response {
+    let mut body = format!("icon not found: {id}\n");
+    let suggestions = suggest(icon_type, name);
+    if !suggestions.is_empty() {
+        body.push_str("did you mean:\n");
+        for suggestion in suggestions {
+            body.push_str(&format!("  {suggestion}\n"));
+        }
+    }
+    (StatusCode::NOT_FOUND, body).into_response()
+}
+
+fn suggest(icon_type: Option<IconType>, name: &str) -> Vec<String> {
+    let mut suggestions: Vec<_> = closest(icon_type, name).iter().map(icon_url).collect();
+    if suggestions.is_empty() && icon_type.is_none() {
+        suggestions = IconType::all()
+            .into_iter()
+            .map(|t| format!("/icons/{}/{}/", t.set(), t.variant()))
+            .collect();
+    }
+    suggestions
+}
+
+/// the url of the icon in `icon_router`
+pub(crate) fn icon_url(icon: &Icon) -> String {
+    format!(
+        "/icons/{}/{}/{}.svg",
+        icon.set(),
+        icon.variant(),
+        icon.name()
+    )
+}
+
+/// the icons with names close to the name, preferring the icon type
+pub(crate) fn closest(icon_type: Option<IconType>, name: &str) -> Vec<Icon<'static>> {
+    let mut found: Vec<_> = IconType::all()
+        .into_iter()
+        .flat_map(|t| t.names().into_iter().map(move |n| (t, n)))
+        .filter_map(|(t, n)| {
+            let distance = distance(name, n);
+            let close =
+                distance <= (name.len() / 3).max(2) || (name.len() >= 3 && n.contains(name));
+            close.then_some(((Some(t) != icon_type, distance, n.len()), (t, n)))
+        })
+        .collect();
+    found.sort();
+    found
+        .into_iter()
+        .take(5)
+        .filter_map(|(_, (t, n))| icon(t, n))
+        .collect()
+}
+
+/// the Levenshtein distance between two names
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            let next = (row[j + 1] + 1).min(row[j] + 1).min(prev + cost);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    async fn send(uri: &str, headers: &[(&str, &str)]) -> (StatusCode, HeaderMap, Vec<u8>) {
+        let mut request = Request::get(uri);
+        for (k, v) in headers {
+            request = request.header(*k, *v);
+        }
+        let response = icon_router()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let (parts, body) = response.into_parts();
+        let body = body.collect().await.unwrap().to_bytes().to_vec();
+        (parts.status, parts.headers, body)
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[tokio::test]
+    async fn icon_router_should_serve_icons() {
+        let (status, headers, body) = send(
+            "/icons/heroicons/outline/trash.svg?size=24&color=%23f00",
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_TYPE], "image/svg+xml");
+        assert_eq!(
+            headers[header::CACHE_CONTROL],
+            "public, max-age=31536000, immutable"
+        );
+        let svg = String::from_utf8(body).unwrap();
+        assert!(svg.starts_with("<svg viewBox=\"0 0 24 24\" "));
+        assert!(svg.contains(" color=\"#f00\""));
+        // the size replaces the width and height of the icon
+        assert_eq!(svg.matches(" width=\"24\"").count(), 1);
+        assert_eq!(svg.matches(" height=\"24\"").count(), 1);
+
+        // the ETag is strong and stable
+        let etag = headers[header::ETAG].to_str().unwrap().to_owned();
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        let uri = "/icons/heroicons/outline/trash.svg?size=24&color=%23f00";
+        let (status, headers, body) = send(uri, &[("if-none-match", &etag)]).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(headers[header::ETAG], etag.as_str());
+        assert!(body.is_empty());
+        let (status, ..) = send(
+            "/icons/heroicons/outline/trash.svg",
+            &[("if-none-match", &etag)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[tokio::test]
+    async fn icon_router_should_compress() {
+        let (_, _, plain) = send("/icons/heroicons/outline/trash.svg", &[]).await;
+
+        let (_, headers, body) = send(
+            "/icons/heroicons/outline/trash.svg",
+            &[("accept-encoding", "gzip, deflate, br")],
+        )
+        .await;
+        assert_eq!(headers[header::CONTENT_ENCODING], "br");
+        assert_eq!(headers[header::VARY], "Accept-Encoding");
+        assert!(headers[header::ETAG].to_str().unwrap().ends_with("-br\""));
+        let mut decoded = Vec::new();
+        brotli::BrotliDecompress(&mut body.as_slice(), &mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+
+        let (_, headers, body) = send(
+            "/icons/heroicons/outline/trash.svg",
+            &[("accept-encoding", "br;q=0.5, gzip")],
+        )
+        .await;
+        assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(body.as_slice())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plain);
+
+        let (_, headers, _) = send(
+            "/icons/heroicons/outline/trash.svg",
+            &[("accept-encoding", "br;q=0, identity")],
+        )
+        .await;
+        assert!(headers.get(header::CONTENT_ENCODING).is_none());
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[tokio::test]
+    async fn icon_router_should_reject_bad_requests() {
+        let (status, _, body) = send("/icons/heroicons/outline/trahs.svg", &[]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.starts_with("icon not found: heroicons:outline:trahs\ndid you mean:\n  /icons/heroicons/outline/trash.svg\n"));
+
+        let (status, _, body) = send("/icons/nope/outline/trash.svg", &[]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("/icons/heroicons/outline/trash.svg"));
+
+        let (status, _, body) = send(
+            "/icons/heroicons/outline/trash.svg?color=%22%3E%3Cscript%3E",
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .starts_with("invalid value for `color`"));
+
+        let (status, _, body) = send("/icons/heroicons/outline/trash.svg?onload=x", &[]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .starts_with("unknown parameter `onload`"));
+    }
+
+    #[test]
+    fn values_should_be_validated() {
+        assert!(is_length("24") && is_length("1.5em") && is_length("100%"));
+        assert!(!is_length("") && !is_length("-1") && !is_length("24 onload") && !is_length("1e"));
+        assert!(is_color("#f00") && is_color("#ff000080") && is_color("currentColor"));
+        assert!(!is_color("#ff") && !is_color("red;") && !is_color("url(#a)"));
+        assert_eq!(distance("trahs", "trash"), 2);
+    }
+}
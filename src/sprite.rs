@@ -0,0 +1,212 @@
+use crate::{render::write_escaped, Element, Icon, IconAttrs, IconDocument};
+use std::{cell::RefCell, collections::BTreeMap, fmt, fs, io, path::Path};
+
+/// root attributes of an icon which are not copied to the references
+const DROPPED_ATTRS: &[&str] = &["xmlns", "xmlns:xlink", "version", "xml:space", "id"];
+
+/// collect the icons used during a render into one SVG sprite of `<symbol>`s,
+/// and render the icons as tiny `<svg><use href="#..."/></svg>` references
+#[derive(Debug, Clone)]
+pub struct SpriteBuilder {
+    id_prefix: String,
+    href: String,
+    symbols: RefCell<BTreeMap<String, Element>>,
+}
+
+impl Default for SpriteBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpriteBuilder {
+    /// a sprite with symbol ids like `icon-heroicons-outline-trash`, to be
+    /// inlined into the page
+    pub fn new() -> Self {
+        Self {
+            id_prefix: "icon-".into(),
+            href: String::new(),
+            symbols: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.id_prefix = prefix.into();
+        self
+    }
+
+    /// reference the symbols in an external sprite file at the url, e.g.
+    /// `/static/icons.svg`, instead of an inlined sprite
+    pub fn external(mut self, url: impl Into<String>) -> Self {
+        self.href = url.into();
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.borrow().len()
+    }
+
+    /// add the icon to the sprite, and return the reference to it
+    pub fn use_icon(&self, icon: &Icon) -> String {
+        let mut out = String::new();
+        // writing into a String never fails
+        let _ = self.render_use_to(&mut out, icon);
+        out
+    }
+
+    /// add the icon to the sprite, and write the reference to it into `w`. The
+    /// attributes of the icon and of its root are set on the reference, so the
+    /// symbol inherits them, and only the element styles and animations are
+    /// baked into a symbol of their own.
+    pub fn render_use_to(&self, w: &mut impl fmt::Write, icon: &Icon) -> fmt::Result {
+        let attrs = icon.get_attrs();
+        let body = IconAttrs {
+            styles: attrs.styles.clone(),
+            animation: attrs.animation,
+            ..Default::default()
+        }
+        .transform(icon.body())
+        .map_err(|_| fmt::Error)?;
+        let Ok(doc) = IconDocument::parse(&body) else {
+            return icon.render_to(w);
+        };
+
+        let id = self.add(icon, doc.clone());
+        w.write_str("<svg")?;
+        attrs.write_attrs(w)?;
+        for (k, v) in doc.root().attrs() {
+            let overridden = attrs.pairs().any(|(name, _)| name.eq_ignore_ascii_case(k));
+            if !DROPPED_ATTRS.contains(&k) && !overridden {
+                write!(w, " {k}=\"{v}\"")?;
+            }
+        }
+        w.write_str("><use href=\"")?;
+        write_escaped(w, &self.href)?;
+        write!(w, "#{id}\"/></svg>")
+    }
+
+    /// the hidden sprite to inline into the page, before the references
+    pub fn build(&self) -> String {
+        let mut root = self.sprite();
+        root.set_attr("style", "display:none");
+        IconDocument::new(root).to_string()
+    }
+
+    /// the sprite as a standalone SVG file, for `external` references
+    pub fn to_xml(&self) -> String {
+        IconDocument::new(self.sprite()).to_xml()
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_xml())
+    }
+
+    fn sprite(&self) -> Element {
+        let mut root = Element::new("svg");
+        root.set_attr("xmlns", "http://www.w3.org/2000/svg");
+        for symbol in self.symbols.borrow().values() {
+            root.push(symbol.clone());
+        }
+        root
+    }
+
+    /// add the symbol if it is not in the sprite yet, and return its id
+    fn add(&self, icon: &Icon, doc: IconDocument) -> String {
+        let base = format!(
+            "{}{}-{}-{}",
+            self.id_prefix,
+            icon.set(),
+            icon.variant(),
+            icon.name()
+        );
+        let mut symbols = self.symbols.borrow_mut();
+
+        // the same icon with other element styles or animations gets a
+        // symbol of its own
+        let mut n = 1;
+        loop {
+            let id = match n {
+                1 => base.clone(),
+                _ => format!("{base}-{n}"),
+            };
+            let mut candidate = doc.clone();
+            candidate.rewrite_ids(&id);
+            let symbol = to_symbol(&id, candidate.root());
+            match symbols.get(&id) {
+                Some(existing) if *existing == symbol => return id,
+                Some(_) => n += 1,
+                None => {
+                    symbols.insert(id.clone(), symbol);
+                    return id;
+                }
+            }
+        }
+    }
+}
+
+/// the symbol only keeps the `viewBox` of the root: its other attributes, like
+/// `fill` or `stroke`, are set on the references to be inherited
+fn to_symbol(id: &str, root: &Element) -> Element {
+    let mut symbol = Element::new("symbol");
+    symbol.set_attr("id", id);
+    for (k, v) in root.attrs() {
+        if k.eq_ignore_ascii_case("viewbox") {
+            symbol.set_attr(k, v);
+        }
+    }
+    for child in root.children() {
+        symbol.push(child.clone());
+    }
+    symbol
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IconType;
+
+    #[cfg(feature = "ionicons")]
+    #[test]
+    fn sprite_should_dedup_symbols() {
+        let sprite = SpriteBuilder::new();
+        let icon = crate::icon(IconType::Ionicons(crate::Ionicons::Normal), "prism").unwrap();
+        let first = sprite.use_icon(&icon.clone().class("h-4"));
+        let second = sprite.use_icon(&icon.clone().class("h-8"));
+        assert_eq!(
+            first,
+            r##"<svg class="h-4" viewbox="0 0 512 512"><use href="#icon-ionicons-normal-prism"/></svg>"##
+        );
+        assert!(second.starts_with(r#"<svg class="h-8" "#));
+        // the paint is inherited from the reference
+        let red = sprite.use_icon(&icon.clone().fill("red"));
+        assert!(red.starts_with(r#"<svg fill="red" "#));
+        assert_eq!(sprite.len(), 1);
+
+        sprite.use_icon(&icon.duotone("red", "blue"));
+        assert_eq!(sprite.len(), 2);
+
+        let html = sprite.build();
+        assert!(html.starts_with("<svg xmlns=http://www.w3.org/2000/svg style=display:none><symbol id=icon-ionicons-normal-prism viewbox=\"0 0 512 512\">"));
+        assert!(html.contains("<symbol id=icon-ionicons-normal-prism-2 "));
+    }
+
+    #[cfg(feature = "bootstrap")]
+    #[test]
+    fn external_sprite_should_work() {
+        let sprite = SpriteBuilder::new()
+            .id_prefix("i-")
+            .external("/icons.svg?v=\"1\"");
+        let icon = crate::icon(IconType::Bootstrap(crate::Bootstrap::Fill), "alarm").unwrap();
+        assert_eq!(
+            sprite.use_icon(&icon),
+            r#"<svg class="bi bi-alarm-fill" viewbox="0 0 16 16" fill="currentColor" height="16" width="16"><use href="/icons.svg?v=&quot;1&quot;#i-bootstrap-fill-alarm"/></svg>"#
+        );
+        assert!(sprite
+            .to_xml()
+            .starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg"><symbol id="i-bootstrap-fill-alarm" viewBox="0 0 16 16">"#));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Element, IconDocument, IconError};
+use std::borrow::Cow;
+
+/// elements which draw something, addressed by `Selector::Index` and `Selector::All`
+const SHAPES: &[&str] = &[
+    "path", "circle", "ellipse", "line", "polyline", "polygon", "rect",
+];
+
+/// addresses elements of an icon, in document order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Selector<'a> {
+    /// every shape (`*`)
+    All,
+    /// the n-th shape, counting from 0 (`*:nth(1)`)
+    Index(usize),
+    /// every element with the name (`path`)
+    Name(&'a str),
+    /// the n-th element with the name, counting from 0 (`path:nth(1)`)
+    Nth(&'a str, usize),
+}
+
+/// the presentation attributes to set on the selected elements
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ElementStyle<'a> {
+    color: Option<Cow<'a, str>>,
+    fill: Option<Cow<'a, str>>,
+    stroke: Option<Cow<'a, str>>,
+    opacity: Option<Cow<'a, str>>,
+    class: Option<Cow<'a, str>>,
+}
+
+impl<'a> Selector<'a> {
+    /// parse `*`, `*:nth(1)`, `path` or `path:nth(1)`
+    pub fn parse(s: &'a str) -> Result<Self, IconError> {
+        let err = IconError::Parse {
+            pos: 0,
+            reason: "invalid selector",
+        };
+        let (name, nth) = match s.split_once(":nth(") {
+            Some((name, rest)) => {
+                let n = rest
+                    .strip_suffix(')')
+                    .and_then(|n| n.trim().parse().ok())
+                    .ok_or(err.clone())?;
+                (name, Some(n))
+            }
+            None => (s, None),
+        };
+        if name.is_empty() || !name.bytes().all(|b| b == b'*' || b.is_ascii_alphanumeric()) {
+            return Err(err);
+        }
+
+        Ok(match (name, nth) {
+            ("*", None) => Selector::All,
+            ("*", Some(n)) => Selector::Index(n),
+            (_, None) if !name.contains('*') => Selector::Name(name),
+            (_, Some(n)) if !name.contains('*') => Selector::Nth(name, n),
+            _ => return Err(err),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Selector<'a> {
+    type Error = IconError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(s)
+    }
+}
+
+impl From<usize> for Selector<'_> {
+    fn from(index: usize) -> Self {
+        Selector::Index(index)
+    }
+}
+
+impl<'a> ElementStyle<'a> {
+    /// paint the element with the color: its fill, stroke or both, depending on
+    /// which of them (including the inherited ones) is not `none`
+    pub fn color(mut self, color: &'a str) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn fill(mut self, fill: &'a str) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    pub fn stroke(mut self, stroke: &'a str) -> Self {
+        self.stroke = Some(stroke.into());
+        self
+    }
+
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity.to_string().into());
+        self
+    }
+
+    /// add a class to the element, keeping the existing ones
+    pub fn class(mut self, class: &'a str) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    fn apply(&self, el: &mut Element, paint: &Paint) {
+        if let Some(color) = &self.color {
+            if paint.fill {
+                el.set_attr("fill", color.as_ref());
+            }
+            if paint.stroke {
+                el.set_attr("stroke", color.as_ref());
+            }
+        }
+        if let Some(fill) = &self.fill {
+            el.set_attr("fill", fill.as_ref());
+        }
+        if let Some(stroke) = &self.stroke {
+            el.set_attr("stroke", stroke.as_ref());
+        }
+        if let Some(opacity) = &self.opacity {
+            el.set_attr("opacity", opacity.as_ref());
+        }
+        if let Some(class) = &self.class {
+            let class = match el.attr("class") {
+                Some(existing) if !existing.is_empty() => format!("{existing} {class}"),
+                _ => class.to_string(),
+            };
+            el.set_attr("class", class);
+        }
+    }
+}
+
+/// the paint an element inherits from its ancestors
+#[derive(Debug, Clone, Copy)]
+struct Paint {
+    fill: bool,
+    stroke: bool,
+}
+
+impl Paint {
+    fn of(&self, el: &Element) -> Self {
+        Self {
+            fill: el.attr("fill").map_or(self.fill, |v| v != "none"),
+            stroke: el.attr("stroke").map_or(self.stroke, |v| v != "none"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    shapes: usize,
+    named: usize,
+    matched: usize,
+}
+
+impl IconDocument {
+    /// set the style on the elements matching the selector, and return the
+    /// number of elements changed. The root `<svg>` is never matched.
+    pub fn style(&mut self, selector: &Selector, style: &ElementStyle) -> usize {
+        let paint = Paint {
+            fill: true,
+            stroke: false,
+        }
+        .of(self.root());
+        let mut counters = Counters::default();
+        for el in self.root_mut().elements_mut() {
+            style_element(el, selector, style, paint, &mut counters);
+        }
+        counters.matched
+    }
+
+    /// tint the first shape with the primary color, and the others with the
+    /// secondary color at 40% opacity
+    pub fn duotone(&mut self, primary: &str, secondary: &str) {
+        self.style(
+            &Selector::All,
+            &ElementStyle::default().color(secondary).opacity(0.4),
+        );
+        self.style(
+            &Selector::Index(0),
+            &ElementStyle::default().color(primary).opacity(1.0),
+        );
+    }
+}
+
+fn style_element(
+    el: &mut Element,
+    selector: &Selector,
+    style: &ElementStyle,
+    paint: Paint,
+    counters: &mut Counters,
+) {
+    let paint = paint.of(el);
+    let is_shape = SHAPES.contains(&el.name());
+    let matched = match selector {
+        Selector::All => is_shape,
+        Selector::Index(n) => is_shape && counters.shapes == *n,
+        Selector::Name(name) => el.name() == *name,
+        Selector::Nth(name, n) => el.name() == *name && counters.named == *n,
+    };
+    if is_shape {
+        counters.shapes += 1;
+    }
+    if matches!(selector, Selector::Nth(name, _) if el.name() == *name) {
+        counters.named += 1;
+    }
+    if matched {
+        style.apply(el, &paint);
+        counters.matched += 1;
+    }
+
+    for child in el.elements_mut() {
+        style_element(child, selector, style, paint, counters);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg viewbox="0 0 24 24" fill=none stroke=currentColor xmlns=http://www.w3.org/2000/svg><g><circle cx=12 cy=12 r=10 /><path d="M8 12h8"/></g><path d="M12 8v8" class=a /></svg>"#;
+
+    #[test]
+    fn selector_should_parse() {
+        assert_eq!(Selector::parse("*"), Ok(Selector::All));
+        assert_eq!(Selector::parse("*:nth(2)"), Ok(Selector::Index(2)));
+        assert_eq!(Selector::parse("path"), Ok(Selector::Name("path")));
+        assert_eq!(Selector::parse("path:nth(1)"), Ok(Selector::Nth("path", 1)));
+        assert!(Selector::parse("path:nth(x)").is_err());
+        assert!(Selector::parse("p*th").is_err());
+        assert!(Selector::try_from("path:nth(2").is_err());
+    }
+
+    #[test]
+    fn style_should_match_nested_elements() {
+        let mut doc = IconDocument::parse(SVG).unwrap();
+        let style = ElementStyle::default().opacity(0.5).class("b");
+        assert_eq!(doc.style(&"path:nth(1)".try_into().unwrap(), &style), 1);
+        assert_eq!(
+            doc.style(&0.into(), &ElementStyle::default().fill("red")),
+            1
+        );
+        assert_eq!(
+            doc.to_string(),
+            r#"<svg viewbox="0 0 24 24" fill=none stroke=currentColor xmlns=http://www.w3.org/2000/svg><g><circle cx=12 cy=12 r=10 fill=red /><path d="M8 12h8"/></g><path d="M12 8v8" class="a b" opacity=0.5 /></svg>"#
+        );
+    }
+
+    #[test]
+    fn duotone_should_paint_the_stroke_of_outline_icons() {
+        let mut doc = IconDocument::parse(SVG).unwrap();
+        doc.duotone("red", "blue");
+        assert_eq!(
+            doc.to_string(),
+            r#"<svg viewbox="0 0 24 24" fill=none stroke=currentColor xmlns=http://www.w3.org/2000/svg><g><circle cx=12 cy=12 r=10 stroke=red opacity=1 /><path d="M8 12h8" stroke=blue opacity=0.4 /></g><path d="M12 8v8" class=a stroke=blue opacity=0.4 /></svg>"#
+        );
+    }
+}
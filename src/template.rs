@@ -0,0 +1,308 @@
+use crate::{render::escaped_attr, Icon, IconError};
+
+/// the icon with the id (`set:variant:name`), inline in an HTML template. The
+/// attributes replace the ones of the icon, with `_` in their names read as
+/// `-` (`stroke_width`), and their values are escaped. Icons with ids get a
+/// fresh prefix, so that they can be inlined several times in a page.
+pub fn render_icon<K, V>(
+    id: &str,
+    attrs: impl IntoIterator<Item = (K, V)>,
+) -> Result<String, IconError>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let icon: Icon = id.parse()?;
+    let attrs = attrs
+        .into_iter()
+        .map(|(name, value)| escaped_attr(&name.as_ref().replace('_', "-"), value.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(icon.unique_ids().to_document_with(attrs)?.to_xml())
+}
+
+/// `{{ icon("heroicons:outline:trash", class="h-4") }}` in MiniJinja templates,
+/// or `{{ "heroicons:outline:trash"|icon(class="h-4") }}` as a filter. The
+/// markup is marked as safe, so it is not escaped again.
+#[cfg(feature = "minijinja")]
+pub fn minijinja_icon(
+    id: &str,
+    kwargs: minijinja::value::Kwargs,
+) -> Result<minijinja::Value, minijinja::Error> {
+    use minijinja::{Error, ErrorKind, Value};
+
+    let mut attrs = Vec::new();
+    for name in kwargs.args() {
+        let value: Value = kwargs.get(name)?;
+        if !value.is_none() && !value.is_undefined() {
+            attrs.push((name, value.to_string()));
+        }
+    }
+    kwargs.assert_all_used()?;
+    render_icon(id, attrs)
+        .map(Value::from_safe_string)
+        .map_err(|e| Error::new(ErrorKind::InvalidOperation, e.to_string()))
+}
+
+/// register `minijinja_icon` as the `icon` function and filter
+#[cfg(feature = "minijinja")]
+pub fn add_minijinja_icons(env: &mut minijinja::Environment) {
+    env.add_function("icon", minijinja_icon);
+    env.add_filter("icon", minijinja_icon);
+}
+
+/// `{{ icon(id="heroicons:outline:trash", class="h-4") }}` in Tera templates.
+/// The other arguments are the attributes of the icon, and the markup is
+/// marked as safe.
+#[cfg(feature = "tera")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TeraIcon;
+
+#[cfg(feature = "tera")]
+impl tera::Function for TeraIcon {
+    fn call(
+        &self,
+        args: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        use tera::Value;
+
+        let id = match args.get("id") {
+            Some(Value::String(id)) => id,
+            _ => return Err("`icon` expects a string `id` argument".into()),
+        };
+        // the arguments are a hash map, so sort them for a stable output
+        let mut attrs = std::collections::BTreeMap::new();
+        for (name, value) in args.iter().filter(|(name, _)| *name != "id") {
+            let value = match value {
+                Value::Null => continue,
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => return Err(format!("`icon` expects `{name}` to be a string").into()),
+            };
+            attrs.insert(name, value);
+        }
+        render_icon(id, attrs)
+            .map(Value::String)
+            .map_err(|e| tera::Error::msg(e.to_string()))
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+/// register `TeraIcon` as the `icon` function
+#[cfg(feature = "tera")]
+pub fn add_tera_icons(tera: &mut tera::Tera) {
+    tera.register_function("icon", TeraIcon);
+}
+
+/// filters for Askama templates, which look them up in a `filters` module in
+/// scope: with `mod filters { pub use free_icons::askama_filters::*; }`,
+/// templates can use `{{ "heroicons:outline:trash"|icon("h-4") }}`.
+#[cfg(feature = "askama")]
+pub mod askama_filters {
+    use askama::{filters::Safe, Values};
+    use std::fmt::Display;
+
+    /// the icon with the id, and an optional class and size, as safe markup
+    #[askama::filter_fn]
+    pub fn icon(
+        id: impl Display,
+        _: &dyn Values,
+        #[optional("")] class: &str,
+        #[optional("")] size: &str,
+    ) -> askama::Result<Safe<String>> {
+        let attrs = [("class", class), ("width", size), ("height", size)]
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty());
+        super::render_icon(&id.to_string(), attrs)
+            .map(Safe)
+            .map_err(askama::Error::custom)
+    }
+}
+
+/// splice icons into maud templates, e.g.
+/// `(icon!(Heroicons::Outline, "trash").class("h-4"))`. The icon streams into
+/// the buffer of the template, with the values of its attributes escaped.
+#[cfg(feature = "maud")]
+impl maud::Render for Icon<'_> {
+    fn render_to(&self, buffer: &mut String) {
+        let _ = self.get_attrs().render_html_to(buffer, self.body());
+    }
+}
+
+/// splice icons into markup templates, e.g.
+/// `{icon!(Heroicons::Outline, "trash").class("h-4")}`, like with maud
+#[cfg(feature = "markup")]
+impl markup::Render for Icon<'_> {
+    fn render(&self, writer: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.get_attrs().render_html_to(writer, self.body())
+    }
+}
+
+/// splice icons into hypertext templates, e.g.
+/// `(icon!(Heroicons::Outline, "trash").class("h-4"))` in `maud!`, or
+/// `{icon!(Heroicons::Outline, "trash")}` in `rsx!`, like with maud
+#[cfg(feature = "hypertext")]
+impl hypertext::Renderable for Icon<'_> {
+    fn render_to(&self, buffer: &mut hypertext::Buffer) {
+        // the body of the icon is trusted markup, and the attributes are
+        // escaped
+        let _ = self
+            .get_attrs()
+            .render_html_to(buffer.dangerously_get_string(), self.body());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn render_icon_should_escape_attributes() {
+        let html = render_icon(
+            "heroicons:outline:trash",
+            [("class", "h-4 \"><script>"), ("stroke_width", "2")],
+        )
+        .unwrap();
+        assert!(html.starts_with("<svg "));
+        assert!(html.contains(" class=\"h-4 &quot;&gt;&lt;script&gt;\""));
+        assert!(html.contains(" stroke-width=\"2\""));
+        assert_eq!(html.matches("stroke-width=").count(), 1);
+
+        assert!(render_icon("heroicons:outline:trash", [("onload x", "")]).is_err());
+        assert_eq!(
+            render_icon("heroicons:outline:nope", [("class", "")]),
+            Err(IconError::NotFound("heroicons:outline:nope".into()))
+        );
+    }
+
+    #[cfg(all(feature = "minijinja", feature = "heroicons"))]
+    #[test]
+    fn minijinja_icon_should_work() {
+        let mut env = minijinja::Environment::new();
+        add_minijinja_icons(&mut env);
+        let expected = render_icon("heroicons:outline:trash", [("class", "a&b")]).unwrap();
+        for source in [
+            r#"{{ icon("heroicons:outline:trash", class="a&b") }}"#,
+            r#"{{ "heroicons:outline:trash"|icon(class=cls) }}"#,
+        ] {
+            let html = env
+                .render_str(source, minijinja::context! { cls => "a&b" })
+                .unwrap();
+            assert_eq!(html, expected);
+        }
+        assert!(env
+            .render_str(r#"{{ icon("heroicons:outline:nope") }}"#, ())
+            .is_err());
+    }
+
+    #[cfg(all(feature = "tera", feature = "heroicons"))]
+    #[test]
+    fn tera_icon_should_work() {
+        let mut tera = tera::Tera::default();
+        add_tera_icons(&mut tera);
+        let html = tera
+            .render_str(
+                r#"{{ icon(id="heroicons:outline:trash", class="a&b", stroke_width=2) }}"#,
+                &tera::Context::new(),
+            )
+            .unwrap();
+        let expected = render_icon(
+            "heroicons:outline:trash",
+            [("class", "a&b"), ("stroke_width", "2")],
+        )
+        .unwrap();
+        assert_eq!(html, expected);
+        assert!(tera
+            .render_str(r#"{{ icon(class="h-4") }}"#, &tera::Context::new())
+            .is_err());
+    }
+
+    #[cfg(all(feature = "askama", feature = "heroicons"))]
+    #[test]
+    fn askama_filters_should_work() {
+        use askama::Template;
+
+        mod filters {
+            pub use crate::askama_filters::*;
+        }
+
+        #[derive(Template)]
+        #[template(
+            source = r#"<button>{{ "heroicons:outline:trash"|icon(class) }}</button>"#,
+            ext = "html"
+        )]
+        struct Button<'a> {
+            class: &'a str,
+        }
+
+        let html = Button { class: "a<b" }.render().unwrap();
+        let expected = render_icon("heroicons:outline:trash", [("class", "a<b")]).unwrap();
+        assert_eq!(html, format!("<button>{expected}</button>"));
+    }
+
+    #[cfg(all(feature = "maud", feature = "heroicons"))]
+    #[test]
+    fn maud_render_should_work() {
+        let class = "h-4 \"><script>";
+        let markup = maud::html! {
+            button { (crate::icon!(Heroicons::Outline, "trash").class(class)) }
+        };
+        let icon = crate::icon!(Heroicons::Outline, "trash");
+        let body = icon.body();
+        assert_eq!(
+            markup.into_string(),
+            format!(
+                "<button>{}class=\"h-4 &quot;&gt;&lt;script&gt;\"{}</button>",
+                &body[..5],
+                &body[4..]
+            )
+        );
+    }
+
+    #[cfg(all(feature = "markup", feature = "heroicons"))]
+    #[test]
+    fn markup_render_should_work() {
+        let class = "h-4 \"><script>";
+        let html = markup::new! {
+            button { @crate::icon!(Heroicons::Outline, "trash").class(class) }
+        }
+        .to_string();
+        let icon = crate::icon!(Heroicons::Outline, "trash");
+        let body = icon.body();
+        assert_eq!(
+            html,
+            format!(
+                "<button>{}class=\"h-4 &quot;&gt;&lt;script&gt;\"{}</button>",
+                &body[..5],
+                &body[4..]
+            )
+        );
+    }
+
+    #[cfg(all(feature = "hypertext", feature = "heroicons"))]
+    #[test]
+    fn hypertext_render_should_work() {
+        use hypertext::prelude::*;
+
+        let class = "h-4 \"><script>";
+        let html = maud! {
+            button { (crate::icon!(Heroicons::Outline, "trash").class(class)) }
+        }
+        .render()
+        .into_inner();
+        let icon = crate::icon!(Heroicons::Outline, "trash");
+        let body = icon.body();
+        assert_eq!(
+            html,
+            format!(
+                "<button>{}class=\"h-4 &quot;&gt;&lt;script&gt;\"{}</button>",
+                &body[..5],
+                &body[4..]
+            )
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{Icon, IconError, RgbaImage};
+use std::fmt::Write;
+
+// the pixels of the raster per pixel of the preview, in each direction
+const SAMPLES: u32 = 4;
+
+// the foreground and background colors of the terminal
+type Colors = (Option<[u8; 3]>, Option<[u8; 3]>);
+// a character with its colors
+type Cell = (char, Option<[u8; 3]>, Option<[u8; 3]>);
+
+/// how the pixels are drawn with text
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminalMode {
+    /// `▀` and `▄`, a pixel per half cell, each with its own color
+    #[default]
+    HalfBlock,
+    /// braille dots, 2x4 pixels per cell with a single color
+    Braille,
+}
+
+/// preview icons in a terminal with Unicode characters and ANSI truecolor
+/// escapes, without any graphics protocol
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TerminalRenderer {
+    mode: TerminalMode,
+    width: u32,
+    color: bool,
+    tint: Option<[u8; 3]>,
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerminalRenderer {
+    /// a half block renderer, 16 cells wide and in the colors of the icons
+    pub fn new() -> Self {
+        Self {
+            mode: TerminalMode::default(),
+            width: 16,
+            color: true,
+            tint: None,
+        }
+    }
+
+    pub fn mode(mut self, mode: TerminalMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// the width of an icon in cells. Icons are square and cells are about
+    /// twice as high as wide, so an icon is half as many lines high.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// without color, the icons are drawn in the color of the terminal text
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// draw the icons in one color, e.g. to see dark icons on a dark terminal
+    pub fn tint(mut self, rgb: [u8; 3]) -> Self {
+        self.tint = Some(rgb);
+        self
+    }
+
+    /// the lines of the icon, without trailing newlines. Each line is
+    /// `width` cells wide, and resets its colors at the end.
+    pub fn render_lines(&self, icon: &Icon) -> Result<Vec<String>, IconError> {
+        let (px, py) = match self.mode {
+            TerminalMode::HalfBlock => (1, 2),
+            TerminalMode::Braille => (2, 4),
+        };
+        let size = self.width * px;
+        let image = icon.rasterize(size * SAMPLES, 1.0)?;
+        let rows = size.div_ceil(py);
+        let lines = (0..rows)
+            .map(|row| {
+                let mut line = String::new();
+                let mut colors = (None, None);
+                for col in 0..self.width {
+                    let (c, fg, bg) = match self.mode {
+                        TerminalMode::HalfBlock => self.half_block(&image, col, row),
+                        TerminalMode::Braille => self.braille(&image, col, row),
+                    };
+                    self.write_cell(&mut line, &mut colors, c, fg, bg);
+                }
+                if self.color {
+                    line.push_str("\x1b[0m");
+                }
+                line
+            })
+            .collect();
+        Ok(lines)
+    }
+
+    /// the icon as text, a line per row
+    pub fn render(&self, icon: &Icon) -> Result<String, IconError> {
+        let mut out = String::new();
+        for line in self.render_lines(icon)? {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// the icons side by side, `columns` per row, each with its label below,
+    /// e.g. for the results of a search
+    pub fn render_grid<'a>(
+        &self,
+        icons: impl IntoIterator<Item = (Icon<'a>, String)>,
+        columns: usize,
+    ) -> Result<String, IconError> {
+        let icons = icons
+            .into_iter()
+            .map(|(icon, label)| Ok((self.render_lines(&icon)?, label)))
+            .collect::<Result<Vec<_>, IconError>>()?;
+        let width = self.width as usize;
+        let mut out = String::new();
+        for chunk in icons.chunks(columns.max(1)) {
+            let rows = chunk
+                .iter()
+                .map(|(lines, _)| lines.len())
+                .max()
+                .unwrap_or(0);
+            for row in 0..rows {
+                let cells: Vec<_> = chunk
+                    .iter()
+                    .map(|(lines, _)| lines.get(row).cloned().unwrap_or_else(|| " ".repeat(width)))
+                    .collect();
+                out.push_str(cells.join("  ").trim_end());
+                out.push('\n');
+            }
+            let labels: Vec<_> = chunk
+                .iter()
+                .map(|(_, label)| format!("{:width$}", truncate(label, width)))
+                .collect();
+            out.push_str(labels.join("  ").trim_end());
+            out.push_str("\n\n");
+        }
+        Ok(out)
+    }
+
+    fn half_block(&self, image: &RgbaImage, col: u32, row: u32) -> Cell {
+        let top = self.pixel(image, col, row * 2);
+        let bottom = self.pixel(image, col, row * 2 + 1);
+        match (top, bottom) {
+            (None, None) => (' ', None, None),
+            (Some(top), None) => ('▀', Some(top), None),
+            (None, Some(bottom)) => ('▄', Some(bottom), None),
+            (Some(top), Some(bottom)) if !self.color || top == bottom => ('█', Some(top), None),
+            (Some(top), Some(bottom)) => ('▀', Some(top), Some(bottom)),
+        }
+    }
+
+    fn braille(&self, image: &RgbaImage, col: u32, row: u32) -> Cell {
+        // the bits of the dots, column by column
+        const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        let mut bits = 0;
+        let mut sum = [0u32; 3];
+        let mut count = 0;
+        for (dx, dots) in DOTS.iter().enumerate() {
+            for (dy, bit) in dots.iter().enumerate() {
+                if let Some(rgb) = self.pixel(image, col * 2 + dx as u32, row * 4 + dy as u32) {
+                    bits |= bit;
+                    count += 1;
+                    for (sum, c) in sum.iter_mut().zip(rgb) {
+                        *sum += c as u32;
+                    }
+                }
+            }
+        }
+        // a blank cell is a space, not an empty braille pattern
+        let c = match bits {
+            0 => ' ',
+            bits => char::from_u32(0x2800 + bits).unwrap_or(' '),
+        };
+        let fg = (count > 0).then(|| sum.map(|sum| (sum / count) as u8));
+        (c, fg, None)
+    }
+
+    /// the color of a pixel, or None if it is mostly transparent or out of
+    /// the image. A pixel covers `SAMPLES * SAMPLES` pixels of the image, so
+    /// that strokes thinner than a pixel are still drawn.
+    fn pixel(&self, image: &RgbaImage, x: u32, y: u32) -> Option<[u8; 3]> {
+        let mut alpha = 0;
+        let mut sum = [0u32; 3];
+        for sy in y * SAMPLES..(y + 1) * SAMPLES {
+            for sx in x * SAMPLES..(x + 1) * SAMPLES {
+                if sx >= image.width || sy >= image.height {
+                    continue;
+                }
+                let i = ((sy * image.width + sx) * 4) as usize;
+                let a = image.data[i + 3] as u32;
+                alpha += a;
+                for (sum, c) in sum.iter_mut().zip(&image.data[i..i + 3]) {
+                    *sum += *c as u32 * a;
+                }
+            }
+        }
+        // a quarter of the pixel is enough to draw it
+        if alpha * 4 < 255 * SAMPLES * SAMPLES {
+            return None;
+        }
+        Some(self.tint.unwrap_or(sum.map(|sum| (sum / alpha) as u8)))
+    }
+
+    /// write a cell, with the escapes to change the colors of `current` if
+    /// needed
+    fn write_cell(
+        &self,
+        out: &mut String,
+        current: &mut Colors,
+        c: char,
+        fg: Option<[u8; 3]>,
+        bg: Option<[u8; 3]>,
+    ) {
+        // a space only shows its background
+        let fg = if c == ' ' { current.0 } else { fg };
+        if self.color && (fg, bg) != *current {
+            if (current.0.is_some() && fg.is_none()) || (current.1.is_some() && bg.is_none()) {
+                out.push_str("\x1b[0m");
+                *current = (None, None);
+            }
+            if let Some([r, g, b]) = fg.filter(|_| fg != current.0) {
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+            }
+            if let Some([r, g, b]) = bg.filter(|_| bg != current.1) {
+                let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+            }
+            *current = (fg, bg);
+        }
+        out.push(c);
+    }
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        return s.to_owned();
+    }
+    let mut out: String = s.chars().take(width.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "feather")]
+    #[test]
+    fn terminal_renderer_should_work() {
+        let icon: Icon = "feather:square".parse().unwrap();
+        let plain = TerminalRenderer::new().width(8).color(false);
+        let lines = plain.render_lines(&icon).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.chars().count() == 8));
+        // a stroked square: the first and last columns are drawn on every line
+        assert!(lines[1].starts_with(['█', '▀', '▄']) && lines[1].ends_with(['█', '▀', '▄']));
+        assert_eq!(lines[1].trim(), lines[2].trim());
+
+        let braille = plain
+            .clone()
+            .mode(TerminalMode::Braille)
+            .render(&icon)
+            .unwrap();
+        assert_eq!(braille.lines().count(), 4);
+        assert!(braille
+            .chars()
+            .any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+
+        let colored = TerminalRenderer::new().width(8).tint([255, 0, 0]);
+        let lines = colored.render_lines(&icon).unwrap();
+        assert!(lines[0].contains("\x1b[38;2;255;0;0m"));
+        assert!(lines.iter().all(|line| line.ends_with("\x1b[0m")));
+
+        let grid = plain
+            .render_grid(
+                [
+                    (icon.clone(), "square".to_owned()),
+                    (icon.clone(), "another-square".to_owned()),
+                    (icon, "third".to_owned()),
+                ],
+                2,
+            )
+            .unwrap();
+        let lines: Vec<_> = grid.lines().collect();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[4], "square    another…");
+        assert_eq!(lines[10], "third");
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{document::xml_name, ClassNaming, Element, Icon, IconError, Node};
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+/// export icons as typed React components: a `.tsx` file per icon, an
+/// `index.ts` re-exporting them, and `types/index.d.ts` declaring them for the
+/// compiled JavaScript. The props are spread onto the root `<svg>`, so they
+/// override the attributes of the icon.
+#[derive(Debug, Clone)]
+pub struct TsxBuilder {
+    naming: ClassNaming,
+    suffix: String,
+    // component name -> source
+    components: BTreeMap<String, String>,
+}
+
+impl Default for TsxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TsxBuilder {
+    pub fn new() -> Self {
+        Self {
+            naming: ClassNaming::default(),
+            suffix: "Icon".into(),
+            components: BTreeMap::new(),
+        }
+    }
+
+    /// how the components are named, e.g. `HeroiconsOutlineTrashIcon` for
+    /// `ClassNaming::Full`, or `TrashIcon` for `ClassNaming::Name`
+    pub fn naming(mut self, naming: ClassNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// the suffix of the component names, `Icon` by default
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    pub fn component_name(&self, icon: &Icon) -> String {
+        let mut name = self
+            .naming
+            .class_name("", icon)
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+            .collect::<String>();
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, '_');
+        }
+        name + &self.suffix
+    }
+
+    /// add an icon, rendered with its attributes. An icon with the same
+    /// component name replaces the previous one.
+    pub fn add(&mut self, icon: &Icon) -> Result<&mut Self, IconError> {
+        let name = self.component_name(icon);
+        let doc = icon.to_document()?;
+        let mut jsx = String::new();
+        write_jsx(&mut jsx, doc.root(), 2, true);
+        let source = format!(
+            "import type {{ SVGProps }} from \"react\";\n\n/** {} */\nexport function {name}(props: SVGProps<SVGSVGElement>) {{\n  return (\n{jsx}  );\n}}\n\nexport default {name};\n",
+            icon.id()
+        );
+        self.components.insert(name, source);
+        Ok(self)
+    }
+
+    pub fn extend<'a>(
+        &mut self,
+        icons: impl IntoIterator<Item = Icon<'a>>,
+    ) -> Result<&mut Self, IconError> {
+        for icon in icons {
+            self.add(&icon)?;
+        }
+        Ok(self)
+    }
+
+    /// the files to write, by file name
+    pub fn build(&self) -> BTreeMap<String, String> {
+        let mut files = BTreeMap::new();
+        let mut index = String::from(
+            "import type { SVGProps } from \"react\";\n\nexport type IconProps = SVGProps<SVGSVGElement>;\n\n",
+        );
+        let mut types = String::from(
+            "import type { JSX, SVGProps } from \"react\";\n\nexport type IconProps = SVGProps<SVGSVGElement>;\n\n",
+        );
+        for (name, source) in &self.components {
+            let _ = writeln!(index, "export {{ {name} }} from \"./{name}\";");
+            let _ = writeln!(
+                types,
+                "export declare function {name}(props: IconProps): JSX.Element;"
+            );
+            files.insert(format!("{name}.tsx"), source.clone());
+        }
+        files.insert("index.ts".into(), index);
+        // in a directory of its own, as TypeScript would resolve `./index` to
+        // `index.ts` and never read an `index.d.ts` next to it
+        files.insert("types/index.d.ts".into(), types);
+        files
+    }
+
+    /// write the files into the directory, which is created if needed
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (name, source) in self.build() {
+            let path = dir.join(name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, source)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_jsx(out: &mut String, el: &Element, depth: usize, root: bool) {
+    let indent = "  ".repeat(depth);
+    let name = xml_name(el.name());
+    let _ = write!(out, "{indent}<{name}");
+    for (k, v) in el.attrs() {
+        if k == "style" {
+            let _ = write!(out, " style={{{{ {} }}}}", style_object(v));
+        } else if v.contains('"') {
+            let _ = write!(out, " {}={{{}}}", jsx_name(k), js_string(v));
+        } else {
+            let _ = write!(out, " {}=\"{v}\"", jsx_name(k));
+        }
+    }
+    if root {
+        out.push_str(" {...props}");
+    }
+    if el.children().is_empty() {
+        out.push_str(" />\n");
+        return;
+    }
+
+    out.push_str(">\n");
+    for child in el.children() {
+        match child {
+            Node::Element(child) => write_jsx(out, child, depth + 1, false),
+            // whitespace between elements is not rendered
+            Node::Text(text) if text.trim().is_empty() => {}
+            Node::Text(text) => {
+                let _ = writeln!(out, "{indent}  {{{}}}", js_string(text));
+            }
+        }
+    }
+    let _ = writeln!(out, "{indent}</{name}>");
+}
+
+/// React names the SVG attributes in camel case, except `data-*` and `aria-*`
+fn jsx_name(name: &str) -> String {
+    if name == "class" {
+        return "className".into();
+    }
+    if name.starts_with("data-") || name.starts_with("aria-") {
+        return name.into();
+    }
+    camel_case(xml_name(name))
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' || c == ':' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// a `style` attribute as the properties of a JavaScript object
+fn style_object(style: &str) -> String {
+    style
+        .split(';')
+        .filter_map(|decl| {
+            let (k, v) = decl.split_once(':')?;
+            let k = k.trim();
+            // vendor prefixes are capitalized, e.g. `WebkitMask`
+            let key = match k.strip_prefix('-') {
+                Some(k) => {
+                    let key = camel_case(k);
+                    key[..1].to_ascii_uppercase() + &key[1..]
+                }
+                None => camel_case(k),
+            };
+            Some(format!("{key}: {}", js_string(v.trim())))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn js_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IconDocument;
+
+    #[test]
+    fn jsx_should_use_react_names() {
+        let doc = IconDocument::parse(r#"<svg viewbox="0 0 512 512" class=a stroke-width=2 xmlns:xlink=http://www.w3.org/1999/xlink aria-hidden=true xmlns=http://www.w3.org/2000/svg><path d=M0,0H1 style=fill:none;stroke-linecap:round;-webkit-transform:none /><style>.a{fill:"red"}</style></svg>"#).unwrap();
+        let mut jsx = String::new();
+        write_jsx(&mut jsx, doc.root(), 0, true);
+        assert_eq!(
+            jsx,
+            r#"<svg viewBox="0 0 512 512" className="a" strokeWidth="2" xmlnsXlink="http://www.w3.org/1999/xlink" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" {...props}>
+  <path d="M0,0H1" style={{ fill: "none", strokeLinecap: "round", WebkitTransform: "none" }} />
+  <style>
+    {".a{fill:\"red\"}"}
+  </style>
+</svg>
+"#
+        );
+    }
+
+    #[cfg(all(feature = "heroicons", feature = "bootstrap"))]
+    #[test]
+    fn tsx_builder_should_work() {
+        let mut builder = TsxBuilder::new().naming(ClassNaming::Name);
+        builder
+            .add(&"heroicons:outline:trash".parse().unwrap())
+            .unwrap()
+            .add(&"bootstrap:fill:0-circle".parse().unwrap())
+            .unwrap();
+        let files = builder.build();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            [
+                "TrashIcon.tsx",
+                "_0CircleIcon.tsx",
+                "index.ts",
+                "types/index.d.ts"
+            ]
+        );
+        let trash = &files["TrashIcon.tsx"];
+        assert!(trash.starts_with("import type { SVGProps } from \"react\";\n\n/** heroicons:outline:trash */\nexport function TrashIcon(props: SVGProps<SVGSVGElement>) {\n  return (\n    <svg "));
+        assert!(trash.contains(" strokeWidth=\"1.5\" "));
+        assert!(trash.contains(" {...props}>\n      <path "));
+        assert!(files["index.ts"].ends_with(
+            "export { TrashIcon } from \"./TrashIcon\";\nexport { _0CircleIcon } from \"./_0CircleIcon\";\n"
+        ));
+        assert!(files["types/index.d.ts"].ends_with(
+            "export type IconProps = SVGProps<SVGSVGElement>;\n\nexport declare function TrashIcon(props: IconProps): JSX.Element;\nexport declare function _0CircleIcon(props: IconProps): JSX.Element;\n"
+        ));
+    }
+}
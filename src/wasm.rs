@@ -0,0 +1,122 @@
+use crate::{render::escaped_attr, Icon, IconError, IconType};
+use js_sys::{Array, Object};
+use wasm_bindgen::prelude::*;
+
+// bindings for JavaScript, packaged for Node and bundlers by
+// `scripts/build-wasm.sh` (`make wasm`). The icons are embedded like in Rust,
+// and the icons of a set are only decompressed the first time one of them is
+// used.
+
+/// the icon types, e.g. `heroicons:outline`
+#[wasm_bindgen(js_name = iconTypes)]
+pub fn icon_types() -> Vec<String> {
+    IconType::all().iter().map(|t| t.to_string()).collect()
+}
+
+/// the names of the icons of an icon type, e.g. `heroicons:outline`
+#[wasm_bindgen]
+pub fn names(icon_type: &str) -> Result<Vec<String>, JsError> {
+    let icon_type: IconType = icon_type.parse()?;
+    Ok(icon_type.names().into_iter().map(String::from).collect())
+}
+
+/// the SVG of the icon with the canonical id, e.g. `heroicons:outline:trash`
+#[wasm_bindgen]
+pub fn get(id: &str) -> Option<String> {
+    let icon: Icon = id.parse().ok()?;
+    Some(icon.body().to_owned())
+}
+
+/// the ids of the icons matching the query, best first
+#[wasm_bindgen]
+pub fn search(query: &str, limit: Option<usize>) -> Vec<String> {
+    crate::search(query)
+        .iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|icon| icon.id())
+        .collect()
+}
+
+/// the icon with the attributes of the object, as a standalone XML document,
+/// e.g. `render("feather:x", { class: "h-4", "stroke-width": 2 })`. The
+/// attributes replace the ones of the icon, and their values are escaped.
+/// Invalid names, and values other than strings, numbers and booleans, throw.
+#[wasm_bindgen]
+pub fn render(id: &str, attrs: Option<Object>) -> Result<String, JsError> {
+    let mut pairs = Vec::new();
+    let entries = attrs.map(|attrs| Object::entries(&attrs));
+    for entry in entries.iter().flat_map(Array::iter) {
+        let entry = Array::from(&entry);
+        // the keys of the entries are always strings
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let value = entry.get(1);
+        let Some(value) = value
+            .as_string()
+            .or_else(|| value.as_f64().map(|n| n.to_string()))
+            .or_else(|| value.as_bool().map(|b| b.to_string()))
+        else {
+            return Err(JsError::new(&format!(
+                "unsupported value of attribute {name}"
+            )));
+        };
+        pairs.push((name, value));
+    }
+    Ok(render_with(id, &pairs)?)
+}
+
+fn render_with(id: &str, attrs: &[(String, String)]) -> Result<String, IconError> {
+    let icon: Icon = id.parse()?;
+    let attrs = attrs
+        .iter()
+        .map(|(name, value)| escaped_attr(name, value))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(icon.to_document_with(attrs)?.to_xml())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn wasm_bindings_should_work() {
+        assert!(icon_types().contains(&"heroicons:outline".to_owned()));
+        assert!(names("heroicons:outline")
+            .unwrap()
+            .contains(&"trash".to_owned()));
+        assert_eq!(
+            get("heroicons:outline:trash").as_deref(),
+            crate::get(IconType::Heroicons(crate::Heroicons::Outline), "trash").map(|s| s.as_str())
+        );
+        assert_eq!(get("heroicons:outline:nope"), None);
+        assert_eq!(
+            search("heroicons trash", Some(1)),
+            ["heroicons:outline:trash"]
+        );
+        let trash: Icon = "heroicons:outline:trash".parse().unwrap();
+        assert_eq!(
+            render("heroicons:outline:trash", None).unwrap(),
+            trash.to_xml().unwrap()
+        );
+    }
+
+    #[cfg(feature = "heroicons")]
+    #[test]
+    fn render_should_replace_and_escape_attributes() {
+        let attrs = [
+            ("stroke-width".to_owned(), "2".to_owned()),
+            ("data-label".to_owned(), "\"<&>\"".to_owned()),
+        ]
+        .into_iter()
+        .chain((0..20).map(|i| (format!("data-{i}"), i.to_string())))
+        .collect::<Vec<_>>();
+        let svg = render_with("heroicons:outline:trash", &attrs).unwrap();
+        assert_eq!(svg.matches(" stroke-width=").count(), 1);
+        assert!(svg.contains(" stroke-width=\"2\""));
+        assert!(svg.contains(" data-label=\"&quot;&lt;&amp;&gt;&quot;\""));
+        assert!(svg.contains(" data-0=\"0\"") && svg.contains(" data-19=\"19\""));
+
+        let attrs = [("on click".to_owned(), "x".to_owned())];
+        assert!(render_with("heroicons:outline:trash", &attrs).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "free_icons.h"
+
+int main(void) {
+  char *svg = NULL;
+  assert(free_icons_get("heroicons:outline:trash", &svg) == FREE_ICONS_ERROR_OK);
+  assert(strncmp(svg, "<svg viewBox=\"0 0 24 24\" ", 25) == 0);
+  assert(strstr(svg, " xmlns=\"http://www.w3.org/2000/svg\"") != NULL);
+  free_icons_free_string(svg);
+
+  // the attributes replace the ones of the icon, and are escaped
+  const char *names[] = {"class", "stroke-width", "data-label"};
+  const char *values[] = {"h-4", "2", "\"<&>\""};
+  assert(free_icons_render("heroicons:outline:trash", names, values, 3, &svg) ==
+         FREE_ICONS_ERROR_OK);
+  const char *found_width = strstr(svg, " stroke-width=\"2\"");
+  assert(found_width != NULL);
+  assert(strstr(found_width + 1, " stroke-width=") == NULL);
+  assert(strstr(svg, " stroke-width=\"1.5\"") == NULL);
+  assert(strstr(svg, " class=\"h-4\"") != NULL);
+  assert(strstr(svg, " data-label=\"&quot;&lt;&amp;&gt;&quot;\"") != NULL);
+  assert(strcmp(svg + strlen(svg) - 6, "</svg>") == 0);
+  free_icons_free_string(svg);
+
+  const char *invalid[] = {"on click"};
+  assert(free_icons_render("heroicons:outline:trash", invalid, values, 1, &svg) ==
+         FREE_ICONS_ERROR_RENDER);
+
+  assert(free_icons_get("heroicons:outline:nope", &svg) == FREE_ICONS_ERROR_NOT_FOUND);
+  assert(free_icons_get("nope:nope:nope", &svg) == FREE_ICONS_ERROR_UNKNOWN_ICON_TYPE);
+  assert(free_icons_get(NULL, &svg) == FREE_ICONS_ERROR_INVALID_ARGUMENT);
+
+  char **types = NULL;
+  size_t len = 0;
+  assert(free_icons_icon_types(&types, &len) == FREE_ICONS_ERROR_OK);
+  int found = 0;
+  for (size_t i = 0; i < len; i++) {
+    found |= strcmp(types[i], "heroicons:outline") == 0;
+  }
+  assert(found);
+  free_icons_free_strings(types, len);
+
+  char **icons = NULL;
+  assert(free_icons_names("heroicons:outline", &icons, &len) == FREE_ICONS_ERROR_OK);
+  found = 0;
+  for (size_t i = 0; i < len; i++) {
+    found |= strcmp(icons[i], "trash") == 0;
+  }
+  assert(found);
+  free_icons_free_strings(icons, len);
+  assert(free_icons_names("nope", &icons, &len) == FREE_ICONS_ERROR_UNKNOWN_ICON_TYPE);
+
+  puts("ok");
+  return 0;
+}
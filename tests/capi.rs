@@ -0,0 +1,47 @@
+// build the cdylib, and `tests/capi.c` against it and `include/free_icons.h`,
+// and run the program
+#![cfg(all(feature = "cdylib", feature = "heroicons", target_os = "linux"))]
+
+use std::{env, path::Path, process::Command};
+
+#[test]
+fn c_program_should_work() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // a target dir of its own, as cargo holds the lock of the current one
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capi");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(root)
+        .args(["rustc", "--lib", "--crate-type", "cdylib"])
+        .args(["--no-default-features", "--features", "cdylib,heroicons"])
+        .arg("--target-dir")
+        .arg(&out_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let lib_dir = out_dir.join("debug");
+
+    let program = out_dir.join("capi");
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".into()))
+        .arg(root.join("tests/capi.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lfree_icons")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(output.stdout, b"ok\n");
+}
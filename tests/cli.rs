@@ -0,0 +1,184 @@
+// run the `free-icons` binary and check its output
+#![cfg(all(feature = "cli", feature = "heroicons"))]
+
+use serde_json::Value;
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+fn free_icons(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_free-icons"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = free_icons(args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn list_should_print_sets_and_icons() {
+    let sets = stdout(&["list"]);
+    assert!(sets
+        .lines()
+        .any(|line| line.starts_with("heroicons:outline\t")));
+    let icons = stdout(&["list", "heroicons:outline"]);
+    assert!(icons.lines().any(|line| line == "heroicons:outline:trash"));
+
+    let json: Value = serde_json::from_str(&stdout(&["list", "--format", "json"])).unwrap();
+    assert!(json
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t["set"] == "heroicons" && t["variant"] == "outline"));
+    let json: Value =
+        serde_json::from_str(&stdout(&["list", "heroicons:solid", "--format", "json"])).unwrap();
+    assert!(json
+        .as_array()
+        .unwrap()
+        .contains(&"heroicons:solid:trash".into()));
+
+    let output = free_icons(&["list", "nope"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: "));
+}
+
+#[test]
+fn search_should_print_the_best_matches() {
+    let ids = stdout(&[
+        "search",
+        "trash",
+        "--set",
+        "heroicons:outline",
+        "--limit",
+        "1",
+    ]);
+    assert_eq!(ids, "heroicons:outline:trash\n");
+    let json: Value = serde_json::from_str(&stdout(&[
+        "search",
+        "trash",
+        "--set",
+        "heroicons",
+        "--format",
+        "json",
+    ]))
+    .unwrap();
+    assert!(json
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|id| id.as_str().unwrap().starts_with("heroicons:")));
+}
+
+#[test]
+fn get_should_print_the_icon_in_every_format() {
+    let svg = stdout(&[
+        "get",
+        "heroicons:outline:trash",
+        "--class",
+        "h-4",
+        "--size",
+        "16",
+    ]);
+    assert!(svg.starts_with("<svg "));
+    assert_eq!(svg.matches(" width=").count(), 1);
+    assert!(svg.contains(" class=\"h-4\""));
+    assert!(svg.contains(" height=\"16\""));
+
+    let uri = stdout(&["get", "heroicons:outline:trash", "--format", "data-uri"]);
+    assert!(uri.starts_with("data:image/svg+xml,%3Csvg "));
+    let base64 = stdout(&["get", "heroicons:outline:trash", "--format", "base64"]);
+    assert!(base64.starts_with("data:image/svg+xml;base64,"));
+    let json: Value = serde_json::from_str(&stdout(&[
+        "get",
+        "heroicons:outline:trash",
+        "--format",
+        "json",
+    ]))
+    .unwrap();
+    assert_eq!(json["id"], "heroicons:outline:trash");
+    assert_eq!(
+        json["svg"].as_str().unwrap(),
+        stdout(&["get", "heroicons:outline:trash"]).trim_end()
+    );
+    assert_eq!(json["dataUri"].as_str().unwrap(), uri.trim_end());
+
+    assert!(!free_icons(&["get", "heroicons:outline:nope"])
+        .status
+        .success());
+}
+
+#[test]
+fn export_should_write_files() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-export");
+    let _ = fs::remove_dir_all(&dir);
+    let out = dir.to_str().unwrap();
+    let count = stdout(&[
+        "export",
+        "--set",
+        "heroicons:outline",
+        "--out",
+        out,
+        "--stroke-width",
+        "2",
+    ]);
+    let (icon_type, count) = count.trim_end().split_once('\t').unwrap();
+    assert_eq!(icon_type, "heroicons:outline");
+    let files = fs::read_dir(dir.join("heroicons/outline")).unwrap().count();
+    assert_eq!(files, count.parse::<usize>().unwrap());
+    let trash = fs::read_to_string(dir.join("heroicons/outline/trash.svg")).unwrap();
+    assert_eq!(trash.matches("stroke-width=").count(), 1);
+    assert!(trash.contains(" stroke-width=\"2\""));
+
+    stdout(&[
+        "export",
+        "--set",
+        "heroicons:outline",
+        "--out",
+        out,
+        "--format",
+        "data-uri",
+    ]);
+    let lines = fs::read_to_string(dir.join("heroicons-outline.txt")).unwrap();
+    assert!(lines
+        .lines()
+        .any(|line| line.starts_with("trash data:image/svg+xml,")));
+    stdout(&[
+        "export",
+        "--set",
+        "heroicons:outline",
+        "--out",
+        out,
+        "--format",
+        "json",
+    ]);
+    let json: Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("heroicons-outline.json")).unwrap())
+            .unwrap();
+    assert_eq!(json.as_array().unwrap().len(), files);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn closed_pipe_should_not_fail() {
+    // like `free-icons search e | head -0`, with more output than a pipe holds
+    let mut child = Command::new(env!("CARGO_BIN_EXE_free-icons"))
+        .args(["search", "e", "--limit", "100000"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,21 @@
+// load the package built by `scripts/build-wasm.sh` as its users do: from
+// Node, and as a bundler would (which needs `--experimental-wasm-modules`)
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+
+const node = createRequire(import.meta.url)("../pkg");
+const bundler = await import("../pkg/bundler/free_icons.js");
+
+for (const { iconTypes, names, get, search, render } of [node, bundler]) {
+  assert.ok(iconTypes().includes("heroicons:outline"));
+  assert.ok(names("heroicons:outline").includes("trash"));
+  assert.ok(get("heroicons:outline:trash").startsWith("<svg"));
+  assert.deepEqual(search("heroicons trash", 1), ["heroicons:outline:trash"]);
+
+  const svg = render("heroicons:outline:trash", { class: "h-4", "stroke-width": 2 });
+  assert.equal(svg.match(/ stroke-width=/g).length, 1);
+  assert.ok(svg.includes(' stroke-width="2"') && svg.includes(' class="h-4"'));
+  assert.throws(() => render("heroicons:outline:trash", { "on click": "x" }));
+  assert.throws(() => render("heroicons:outline:trash", { class: null }));
+  assert.throws(() => render("heroicons:outline:nope"));
+}
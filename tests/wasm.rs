@@ -0,0 +1,33 @@
+// run with `make test-wasm`
+#![cfg(all(target_arch = "wasm32", feature = "wasm", feature = "heroicons"))]
+
+use free_icons::wasm::{get, names, render};
+use js_sys::{Object, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn render_should_apply_the_attributes() {
+    let attrs = Object::new();
+    Reflect::set(&attrs, &"class".into(), &"h-4".into()).unwrap();
+    Reflect::set(&attrs, &"stroke-width".into(), &JsValue::from(2)).unwrap();
+    let svg = render("heroicons:outline:trash", Some(attrs)).unwrap();
+    assert!(svg.starts_with("<svg viewBox=\"0 0 24 24\" "));
+    assert_eq!(svg.matches(" stroke-width=").count(), 1);
+    assert!(svg.contains(" stroke-width=\"2\""));
+    assert!(svg.contains(" class=\"h-4\""));
+    assert!(get("heroicons:outline:trash").is_some());
+}
+
+#[wasm_bindgen_test]
+fn errors_should_be_thrown() {
+    assert!(render("heroicons:outline:nope", None).is_err());
+    assert!(names("nope:nope").is_err());
+
+    let invalid = Object::new();
+    Reflect::set(&invalid, &"on click".into(), &"x".into()).unwrap();
+    assert!(render("heroicons:outline:trash", Some(invalid)).is_err());
+    let unsupported = Object::new();
+    Reflect::set(&unsupported, &"class".into(), &JsValue::NULL).unwrap();
+    assert!(render("heroicons:outline:trash", Some(unsupported)).is_err());
+}